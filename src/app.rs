@@ -1,11 +1,46 @@
-use leptos::{leptos_dom::logging::console_log, prelude::*};
+mod map;
+mod paged;
+mod sorted;
+pub mod store_list;
+mod streamed;
+mod toast;
+mod virtualized;
+
+use crate::{
+    clipboard,
+    debounce::{debounce, debounced_signal},
+    generation::Generations,
+    history::History,
+    ids::new_id,
+    markdown, share, storage,
+    time::{format_date, is_overdue, now_ms, parse_date, relative_time},
+};
+use leptos::{
+    ev,
+    prelude::*,
+    server_fn::codec::{StreamingText, TextStream},
+};
 use leptos_meta::*;
 use leptos_router::{
     components::{FlatRoutes, Route, Router},
-    StaticSegment,
+    hooks::{use_location, use_navigate, use_params_map, use_query_map},
+    params::ParamsMap,
+    NavigateOptions, ParamSegment, StaticSegment,
 };
-use reactive_stores::{Patch, Store};
+use map::HomeMap;
+use paged::HomePaged;
+use reactive_stores::{AtKeyed, Patch, Store};
 use serde::{Deserialize, Serialize};
+use sorted::HomeSorted;
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    time::Duration,
+};
+use streamed::HomeStreamed;
+use toast::{provide_toasts, use_toasts, ToastHost, ToastKind};
+use unicode_segmentation::UnicodeSegmentation;
+use virtualized::HomeVirtual;
+use wasm_bindgen::{closure::Closure, JsCast};
 
 pub fn shell(options: LeptosOptions) -> impl IntoView {
     view! {
@@ -27,53 +62,127 @@ pub fn shell(options: LeptosOptions) -> impl IntoView {
     }
 }
 
-#[component]
-pub fn App() -> impl IntoView {
-    provide_meta_context();
+/// Roughly how many bytes the Store took up when last persisted, see `storage::save`. Provided
+/// by `provide_store`, and `None` until the first save.
+#[derive(Clone, Copy)]
+struct StorageUsage(RwSignal<Option<usize>>);
 
-    view! {
-        <Router>
-            <FlatRoutes fallback=|| "Page not found.">
-                <Route path=StaticSegment("") view=Home ssr=leptos_router::SsrMode::InOrder />
-            </FlatRoutes>
-        </Router>
+/// Seeds the shared Store from `get_items`, see `provide_store`.
+#[derive(Clone, Copy)]
+struct StoreSeed {
+    store: Store<Data>,
+    items_resource: Resource<Result<Vec<Item>, ServerFnError>>,
+    seeded: StoredValue<bool>,
+    /// The IDs of the items seeded from `storage::load_cache`, until the Resource's answer has
+    /// reconciled them, see `provide_store`.
+    cached: StoredValue<Option<HashSet<u128>>>,
+}
+
+impl StoreSeed {
+    /// Waits for the shared Store's initial items, so call it within a Suspense before rendering
+    /// anything from the Store. Returns `None` until the Resource has resolved.
+    ///
+    /// Should `get_items` fail, its error is returned instead for the caller to propagate with
+    /// `?` to the nearest ErrorBoundary, rather than panicking. The Store is left unseeded, so a
+    /// later successful fetch still seeds it.
+    ///
+    /// The Store is seeded from the Resource the first time only. Rendering a route again, such
+    /// as navigating back to the list from an item's detail page, keeps whatever the Store holds
+    /// by then, local edits included. Later values from the Resource are merged in by
+    /// `provide_store` instead.
+    ///
+    /// Once seeded the Resource isn't read at all, so a refetch doesn't rerun the caller and
+    /// rebuild everything it rendered.
+    ///
+    /// In the browser, while the Resource is still loading, the Store is seeded from the
+    /// items a previous visit cached instead, see `cached_items`, so there's something to show
+    /// straight away.
+    fn wait(&self) -> Option<Result<(), ServerFnError>> {
+        if self.seeded.get_value() {
+            return Some(Ok(()));
+        }
+        if let Some(items) = self.cached_items() {
+            self.seeded.set_value(true);
+            self.cached
+                .set_value(Some(items.iter().map(|item| item.id).collect()));
+            self.store.items().set(items);
+            return Some(Ok(()));
+        }
+        let items = self.items_resource.get()?;
+        Some(items.map(|items| {
+            self.seeded.set_value(true);
+            self.store.items().set(items);
+        }))
+    }
+
+    /// The items last answered by `get_items`, as cached in localStorage, but only while the
+    /// Resource has yet to resolve and the page isn't hydrating.
+    ///
+    /// The server has no localStorage, so it always renders from the blocking Resource, whose
+    /// value is sent along for hydration to use too. Seeding from the cache whilst hydrating
+    /// would render markup the server never sent. So with the blocking Resource the cache goes
+    /// unused on a first load, and only fills in when rendering in the browser before the
+    /// Resource has an answer, say were it a non-blocking `Resource::new`. The Resource is read
+    /// untracked, so as not to suspend on it.
+    fn cached_items(&self) -> Option<Vec<Item>> {
+        let hydrating =
+            Owner::current_shared_context().is_some_and(|context| context.during_hydration());
+        if !cfg!(feature = "hydrate") || hydrating || self.items_resource.get_untracked().is_some()
+        {
+            return None;
+        }
+        storage::load_cache()
     }
 }
 
-/// It pays to keep sub-components logic simple, espeically when it comes to signal-based reactive
-/// systems such as Leptos.
-///
-/// Each item renders a "Delete" button to remove itself from the Store. One could pass the Store
-/// itself to the component and update the Signal there. But this scatters Signal writes across
-/// your application, making the reactivity decision tree hard to reason about, leading to
-/// convoluted debugging of any emerging errors accessing disposed signals.
+/// Merges `items`, fresh from the server, into the Store rather than replacing its contents.
 ///
-/// I've found it preferable to only pass read-only state down in components, and callbacks
-/// to requests state changes from some some higher level, centralised place.
+/// An item present in both is patched through its keyed Field, so only those of its fields
+/// whose values actually differ notify. Patching the whole `items` Vec instead would compare
+/// items by position, and after any reordering would notify rows on behalf of their neighbours.
 ///
-/// Note the `on_delete` argument. It's important **not** to pass signals back up the reactivity
-/// scope but instead to use raw values, in this case a u128.
-#[component]
-fn Item(
-    #[prop(into)] item: reactive_stores::Field<Item>,
-    on_delete: impl Fn(u128) + Clone + Copy + 'static,
-) -> impl IntoView {
-    view! {
-        <div class="flex gap-2">
-            <div class="flex-grow">{ move || format!("{} ({})", item.value().get(), item.id().get()) }</div>
-            <button
-                class="bg-neutral-200 hover:bg-neutral-300 px-4 py-1 rounded"
-                on:click=move |_| {
-                    let id = item.id().get_untracked();
-                    on_delete(id);
-                }
-            >Delete</button>
-        </div>
+/// Items new to the server are appended. Those only present locally, perhaps not yet saved, are
+/// kept.
+fn merge_items(store: Store<Data>, items: Vec<Item>) {
+    let known = store
+        .items()
+        .with_untracked(|items| items.iter().map(|item| item.id).collect::<HashSet<_>>());
+    let mut added = Vec::new();
+    for item in items {
+        if known.contains(&item.id) {
+            AtKeyed::new(store.items(), item.id).patch(item);
+        } else {
+            added.push(item);
+        }
+    }
+    if !added.is_empty() {
+        store.items().update(|items| items.extend(added));
     }
 }
 
-#[component]
-fn Items() -> impl IntoView {
+/// Creates the one Store shared by every route and provides it via context.
+///
+/// Components retrieve it with `expect_context::<Store<Data>>()`. Those rendering its items
+/// should first, within a Suspense, call `expect_context::<StoreSeed>().wait()`.
+///
+/// # Ownership
+///
+/// A Store, like any signal, belongs to the reactive owner it's created under and is disposed
+/// along with it. Created inside `Items`' render closure, the Store would live only as long as
+/// that closure's current run. Whenever the Resource changed the closure would rerun, disposing
+/// the old Store and creating a fresh one, and any Field, callback, timeout or listener still
+/// holding the old Store would then be touching a disposed signal. That's one of the most common
+/// sources of the impenetrable errors described in `Items`.
+///
+/// Called from `App`, above the Router, the Store instead lives as long as the app itself. It
+/// outlives every route, so navigating between them keeps both the Store and its contents
+/// intact. The flip side is that state created within a route, such as `Items`' undo history,
+/// is still scoped to that route and starts afresh on each visit.
+///
+/// Context follows ownership too. It's visible to every component beneath `App`, but not to
+/// code running outside the reactive tree, such as a `window_event_listener` callback, so
+/// retrieve the Store in the component body and move it into such callbacks.
+fn provide_store() {
     // In Leptos a Resource defines some code that one would like to begin computing
     // on the server imeediately. By default, everything else is first delivered to
     // the browser, and then evaluated.
@@ -113,150 +222,6120 @@ fn Items() -> impl IntoView {
     // - https://docs.rs/leptos_router/latest/leptos_router/enum.SsrMode.html
     //
     // In our case we'd like the InOrder strategy, and this is specified for the
-    // Home componet's route in the App component above.
-    let items_resource =
-        Resource::new_blocking(|| (), move |_| async { get_items().await.unwrap() });
+    // Home componet's route in the App component below.
+    let items_resource = Resource::new_blocking(|| (), move |_| async { get_items().await });
+    let store = Store::new(Data::default());
+    let seeded = StoredValue::new(false);
+    let cached = StoredValue::new(None::<HashSet<u128>>);
+    provide_context(store);
+    provide_context(StoreSeed {
+        store,
+        items_resource,
+        seeded,
+        cached,
+    });
 
-    move || {
-        let store = Store::new(Data {
-            items: items_resource.get().unwrap(),
+    // Two items sharing an ID break the keyed For, but the errors that follow never mention
+    // IDs, see `Data`. So in debug builds every write to the list is checked for them, naming
+    // each ID seen twice. Adding and duplicating always give a fresh ID, so should this ever
+    // warn, look first at whatever brings in items from elsewhere, such as importing or merging
+    // in the server's. Release builds compile the check out altogether.
+    #[cfg(debug_assertions)]
+    Effect::new(move |_| {
+        store.items().with(|items| {
+            let mut seen = HashMap::<u128, &str>::new();
+            for item in items {
+                if let Some(first) = seen.insert(item.id, &item.value) {
+                    leptos::logging::warn!(
+                        "Item ID {} is in the Store more than once, as {first:?} and {:?}. The \
+                         keyed For will misbehave until one is given a fresh ID.",
+                        item.id,
+                        item.value,
+                    );
+                }
+            }
         });
+    });
 
-        // Most pitfalls were encountered deleting a specific item:
-        //
-        // 1. Read the item ID **before** writing the Vec from which it was reactively derived.
-        //
-        // ❌ store.items().update(|items| {
-        //     let position = items.iter().position(|i| i.id == item.id().get()).unwrap();
-        //     items.remove(position);
-        // });
-        //
-        // ✅ let id = item.id().get();
-        // store.items().update(|items| {
-        //     let position = items.iter().position(|i| i.id == id).unwrap();
-        //     items.remove(position);
-        // });
-        //
-        // 2. Item ID must be a copy and not a reference to survive it's disposal by
-        // the subsequent Signal write via, say, `store.items().update(...)`.
-        //
-        // ❌ let id = items.id().read();
-        // let id: &u128 = id.as_borrowed();
-        // store.items().update(...);
-        //
-        // ✅ let id = items.id().get();
-        // store.items().update(...);
-        //
-        let on_delete = move |id: u128| {
-            store.items().update(|items| {
-                let index = items.iter().position(|item| item.id == id).unwrap();
-                items.remove(index);
+    // Refetching the Resource, such as after `get_items` is invalidated, merges the result into
+    // the Store so nothing edited locally is thrown away. The first value is skipped, it having
+    // already seeded the Store (or been superseded by localStorage, below). Failed fetches are
+    // left for the ErrorBoundary to show, and until one succeeds there's no Store to merge into.
+    //
+    // Unless, that is, the Store was seeded from the cache of a previous visit, in which case
+    // the first value is merged in too, stale while revalidating. As well as patching and adding
+    // items, the merge leaves out any cached item the server no longer has, while keeping
+    // those added locally in the meantime.
+    Effect::new(move |seen: Option<Option<()>>| {
+        let result = items_resource.get()?;
+        if let Ok(items) = result {
+            if let Some(stale) = cached.try_update_value(Option::take).flatten() {
+                let fresh = items.iter().map(|item| item.id).collect::<HashSet<_>>();
+                store.items().update(|items| {
+                    items.retain(|item| !stale.contains(&item.id) || fresh.contains(&item.id));
+                });
+                merge_items(store, items);
+            } else if seen == Some(Some(())) && seeded.get_value() {
+                merge_items(store, items);
+            }
+        }
+        Some(())
+    });
+
+    // Every successful answer is cached for the next visit, see `StoreSeed::cached_items`.
+    Effect::new(move |_| {
+        if let Some(Ok(items)) = items_resource.get() {
+            storage::save_cache(&items);
+        }
+    });
+
+    // The Store is first rendered from the Resource on both the server and the client, so
+    // hydration sees identical markup. Only afterwards, in an Effect (which never runs during
+    // SSR), is anything previously persisted swapped in. This counts as seeding the Store, so
+    // the Resource won't later overwrite it.
+    //
+    // A large list is persisted to IndexedDB rather than localStorage, see `storage::save`, and
+    // reading it back is asynchronous, so it may arrive a moment after the Resource's list has
+    // been rendered.
+    Effect::new(move |_| {
+        leptos::task::spawn_local(async move {
+            if let Some(data) = storage::load().await {
+                seeded.set_value(true);
+                restore(store, data);
+            }
+        });
+    });
+
+    // Reading the whole Store tracks every field within it, so this changes after any mutation,
+    // whichever route made it. Writes are debounced by 250ms so rapid edits don't thrash
+    // storage. Nothing is written until the Store has been seeded, lest visiting another route
+    // first persist an empty list.
+    //
+    // Should a write fail, the Store is left as it is and a toast says why. Writes keep failing
+    // once storage is full, so the toast isn't repeated until a different error comes along, or
+    // a write has succeeded in between.
+    let toasts = use_toasts();
+    let usage = RwSignal::new(None::<usize>);
+    let last_error = StoredValue::new(None::<String>);
+    provide_context(StorageUsage(usage));
+    debounce(
+        Signal::derive(move || store.get()),
+        Duration::from_millis(250),
+        move |data| {
+            if seeded.get_value() {
+                leptos::task::spawn_local(async move {
+                    match storage::save(data).await {
+                        Ok(bytes) => {
+                            usage.set(Some(bytes));
+                            last_error.set_value(None);
+                        }
+                        Err(error) => {
+                            if last_error.get_value().as_ref() != Some(&error) {
+                                toasts.push_toast(error.clone(), ToastKind::Error);
+                            }
+                            last_error.set_value(Some(error));
+                        }
+                    }
+                });
+            }
+        },
+    );
+}
+
+/// Creates the Store of `Preferences` and provides it via context, persisting it to localStorage
+/// apart from `Data`.
+///
+/// The server can't see localStorage, so it always renders with the defaults, and hydration has
+/// to match. The saved preferences are only read by the Effect below, once hydrated, so a
+/// compact list renders comfortable for a moment on a first load before tightening up. Any
+/// change to them is saved by the second Effect, which first runs just after the first, so it
+/// saves what was loaded rather than overwriting it with the defaults.
+fn provide_preferences() {
+    let preferences = Store::new(Preferences::default());
+    Effect::new(move |_| {
+        if let Some(saved) = storage::load_preferences() {
+            preferences.set(saved);
+        }
+    });
+    Effect::new(move |_| storage::save_preferences(&preferences.get()));
+    provide_context(preferences);
+}
+
+/// How far the window was scrolled when each route was last left, keyed by its path and query,
+/// see `restore_scroll`. Provided by `App`, so it outlives the routes themselves.
+#[derive(Clone, Copy)]
+struct ScrollPositions(StoredValue<HashMap<String, f64>>);
+
+/// Scrolls the window back to where it was when the current route was last left, once
+/// `container` has been rendered, and remembers where it's left this time.
+///
+/// The router renders each route afresh, so coming back to the list from an item's page would
+/// otherwise land at the top, however far down the item was. The offset is taken from the last
+/// scroll event before leaving rather than when the route is cleaned up, as by then the list may
+/// already have been swapped for the next route, the page shrinking and the browser clamping
+/// the offset to fit. Restoring waits a frame, so it comes after the router's own scroll to the
+/// top, and the rows have been laid out. The list may have changed length since, so the offset
+/// is clamped to how far the page can now scroll.
+///
+/// Positions are keyed by path and query alike, so a filtered list only restores to a filtered
+/// list's position, and each is forgotten once restored. They're only kept in memory, a reload
+/// being left to the browser's own restoration.
+fn restore_scroll(container: NodeRef<leptos::html::Div>) {
+    let ScrollPositions(positions) = expect_context::<ScrollPositions>();
+    let location = use_location();
+    let key = move || {
+        format!(
+            "{}{}",
+            location.pathname.get_untracked(),
+            location.search.get_untracked()
+        )
+    };
+    Effect::new(move |_| {
+        if container.get().is_none() {
+            return;
+        }
+        if let Some(offset) = positions
+            .try_update_value(|positions| positions.remove(&key()))
+            .flatten()
+        {
+            request_animation_frame(move || {
+                let height = document()
+                    .document_element()
+                    .map_or(0.0, |root| f64::from(root.scroll_height()));
+                let viewport = window()
+                    .inner_height()
+                    .ok()
+                    .and_then(|height| height.as_f64())
+                    .unwrap_or(0.0);
+                window().scroll_to_with_x_and_y(0.0, offset.min(height - viewport).max(0.0));
             });
-        };
+        }
+        let last = StoredValue::new(None::<(String, f64)>);
+        let listener = window_event_listener(leptos::ev::scroll, move |_| {
+            last.set_value(Some((key(), window().scroll_y().unwrap_or(0.0))));
+        });
+        on_cleanup(move || {
+            listener.remove();
+            if let Some((key, offset)) = last.try_get_value().flatten() {
+                positions.update_value(|positions| {
+                    positions.insert(key, offset);
+                });
+            }
+        });
+    });
+}
 
-        view! {
-            <div class="flex gap-2 mb-4">
+#[component]
+pub fn App() -> impl IntoView {
+    provide_meta_context();
+    provide_toasts();
+    provide_store();
+    provide_preferences();
+    provide_context(ScrollPositions(StoredValue::new(HashMap::new())));
+
+    view! {
+        <ToastHost>
+            <Router>
+                <FlatRoutes fallback=|| "Page not found.">
+                    <Route path=StaticSegment("") view=Home ssr=leptos_router::SsrMode::InOrder />
+                    <Route
+                        path=(StaticSegment("item"), ParamSegment("id"))
+                        view=ItemDetail
+                        ssr=leptos_router::SsrMode::InOrder
+                    />
+                    <Route path=StaticSegment("map") view=HomeMap ssr=leptos_router::SsrMode::InOrder />
+                    <Route path=StaticSegment("sorted") view=HomeSorted ssr=leptos_router::SsrMode::InOrder />
+                    <Route path=StaticSegment("virtual") view=HomeVirtual />
+                    <Route path=StaticSegment("paged") view=HomePaged ssr=leptos_router::SsrMode::InOrder />
+                    <Route path=StaticSegment("streamed") view=HomeStreamed ssr=leptos_router::SsrMode::InOrder />
+                </FlatRoutes>
+            </Router>
+        </ToastHost>
+    }
+}
+
+/// What a row of `Items` can ask of the list, provided as context by `Items` rather than
+/// handed to each `Item` as a prop apiece, just as `Toasts` is.
+///
+/// It's important **not** to pass signals back up the reactivity scope but instead to use raw
+/// values, so each callback takes the raw ID of the row's item, along with whatever's asked for.
+/// They're `Callback`s, rather than the `impl Fn(u128) + Copy` passed as props elsewhere, as a
+/// context can't be generic. They're just as cheap to copy, and are called with `run`.
+#[derive(Clone, Copy)]
+struct ItemCallbacks {
+    on_select: Callback<u128>,
+    on_delete: Callback<u128>,
+    /// Inserts a copy of the item directly after it.
+    on_duplicate: Callback<u128>,
+    on_copy: Callback<u128>,
+    /// Splits the item into an item per part of its value, see `split_value`.
+    on_split: Callback<u128>,
+    /// Passes a change in quantity, rather than the new quantity, leaving `Items` to apply it
+    /// to whatever the Store holds at the time.
+    on_quantity: Callback<(u128, i64)>,
+    on_toggle: Callback<u128>,
+    on_favorite: Callback<u128>,
+    /// Passes the tag as typed, leaving `Items` to trim and dedupe.
+    on_add_tag: Callback<(u128, String)>,
+    on_remove_tag: Callback<(u128, String)>,
+    on_add_child: Callback<(u128, String)>,
+    /// Passes the item's ID and the sub-item's.
+    on_remove_child: Callback<(u128, u128)>,
+    on_expand: Callback<u128>,
+    on_description: Callback<(u128, Option<String>)>,
+    on_status: Callback<(u128, Status)>,
+    on_color: Callback<(u128, Option<ColorLabel>)>,
+    on_due: Callback<(u128, Option<i64>)>,
+    /// Passes the newly committed value, which `Items` checks again before writing it, see
+    /// `ValueRules::check_edit`.
+    on_edit: Callback<(u128, String)>,
+    /// Asked whilst editing whether another item already has the value typed, answering with
+    /// `Items`' `UniqueValues`, or `None` if there isn't one or nobody minds.
+    check_duplicate: Callback<(u128, String), Option<UniqueValues>>,
+    /// Asks for the item to swap places with its neighbour.
+    on_move: Callback<(u128, Direction)>,
+    /// Reports when dragging the row starts, with `Some(id)`, and ends, with `None`.
+    on_drag: Callback<Option<u128>>,
+    /// Reports which row is being dragged over, so `Items` can decide where `drop_indicator`
+    /// is drawn.
+    on_drag_over: Callback<u128>,
+    /// Passes the dropped item's ID, carried in the drag's `dataTransfer`, and this row's.
+    on_drop: Callback<(u128, u128)>,
+    /// Reports focus moving anywhere into the row.
+    on_focus_row: Callback<u128>,
+    /// Passes which way the Up and Down arrows ask to move whilst the row has focus.
+    on_nav: Callback<(u128, Direction)>,
+    /// Passes where the row was right clicked, for the context menu.
+    on_context_menu: Callback<(u128, i32, i32)>,
+}
+
+/// It pays to keep sub-components logic simple, espeically when it comes to signal-based reactive
+/// systems such as Leptos.
+///
+/// Each item renders a "Delete" button to remove itself from the Store. One could pass the Store
+/// itself to the component and update the Signal there. But this scatters Signal writes across
+/// your application, making the reactivity decision tree hard to reason about, leading to
+/// convoluted debugging of any emerging errors accessing disposed signals.
+///
+/// I've found it preferable to only pass read-only state down in components, and callbacks
+/// to requests state changes from some some higher level, centralised place, here the
+/// `ItemCallbacks` provided by `Items`.
+///
+/// The row shows the value, which opens for editing when clicked, a control for each of the
+/// item's fields, with its description and sub-items beneath, rendered by `Description` and
+/// `SubItems`. It can be focused and driven from the keyboard too: the arrows move between
+/// rows, Enter edits and Delete deletes.
+#[component]
+fn Item(
+    #[prop(into)] item: reactive_stores::Field<Item>,
+    /// Whether the row is first or last, disabling the buttons that would move it off either
+    /// end of the list.
+    #[prop(into)]
+    first: Signal<bool>,
+    #[prop(into)] last: Signal<bool>,
+    #[prop(into)] drop_indicator: Signal<Option<Direction>>,
+    #[prop(into)] selected: Signal<bool>,
+    /// Marked wherever it matches the value, see `split_matches`.
+    #[prop(into)]
+    highlight: Signal<String>,
+    /// Renders any Markdown the value holds, through `markdown::to_html` and the `inner_html`
+    /// of a span, which is safe only because that escapes everything taken from the value, see
+    /// its docs. Editing still opens the value as written, and clicking a link in it follows
+    /// the link rather than opening it for editing.
+    #[prop(into)]
+    markdown: Signal<bool>,
+    /// Whilst the server is deleting the item, disables the Delete button, showing a spinner.
+    #[prop(into)]
+    deleting: Signal<bool>,
+    /// Whilst the server has yet to answer for the item, disables every control in the row.
+    #[prop(into)]
+    busy: Signal<bool>,
+    #[prop(into)] expanded: Signal<bool>,
+    /// The time how long ago the item was created, and whether it's overdue, are worked out
+    /// against, handed down from `Items` so every row ticks over together. It's `None` during
+    /// SSR and hydration, so neither depends on whose clock rendered the row.
+    #[prop(into)]
+    now: Signal<Option<i64>>,
+    /// Badges a row whose value another shares, however it came to be.
+    #[prop(into)]
+    duplicated: Signal<bool>,
+    /// Puts the row in the tab order, see `Items`.
+    #[prop(into)]
+    tabbable: Signal<bool>,
+    /// Opens the value for editing, as the context menu's Edit asks, see `edit_request`.
+    #[prop(into)]
+    edit_requested: Signal<bool>,
+    /// Caps how many characters the value may have, counted beneath the input as they're
+    /// typed, see `value_length`. Past it the input is outlined in red, and whether Enter then
+    /// refuses the value or passes it up to be cut short is `over_length`'s to say.
+    max_length: Option<usize>,
+    #[prop(optional)] over_length: OverLength,
+    /// Begins with the value's input open and focused, which is how `Items` hands a freshly
+    /// added item straight to the keyboard.
+    #[prop(optional)]
+    start_editing: bool,
+) -> impl IntoView {
+    let ItemCallbacks {
+        on_select,
+        on_delete,
+        on_duplicate,
+        on_copy,
+        on_split,
+        on_quantity,
+        on_toggle,
+        on_favorite,
+        on_add_tag,
+        on_remove_tag,
+        on_add_child,
+        on_remove_child,
+        on_expand,
+        on_description,
+        on_status,
+        on_color,
+        on_due,
+        on_edit,
+        check_duplicate,
+        on_move,
+        on_drag,
+        on_drag_over,
+        on_drop,
+        on_focus_row,
+        on_nav,
+        on_context_menu,
+    } = expect_context::<ItemCallbacks>();
+    // Whether a row is being edited is view state belonging to this row alone, so it lives in
+    // a plain signal here rather than in the Store, as does `draft`, whatever the input holds
+    // whilst it is. The draft is taken from the item afresh each time editing opens, through
+    // `open_editor`, so it never starts from an edit that was abandoned.
+    let editing = RwSignal::new(start_editing);
+    let draft = RwSignal::new(item.value().get_untracked());
+    let open_editor = move || {
+        draft.set(item.value().get_untracked());
+        editing.set(true);
+    };
+    let error = RwSignal::new(None::<String>);
+    let duplicate = RwSignal::new(None::<UniqueValues>);
+    // The count follows the draft keystroke by keystroke, measuring it as it would be saved.
+    let length = Memo::new(move |_| draft.with(|draft| value_length(&normalize_value(draft))));
+    let over_limit = move || max_length.is_some_and(|max| length.get() > max);
+    let input_ref = NodeRef::<leptos::html::Input>::new();
+    let row_ref = NodeRef::<leptos::html::Div>::new();
+    // Once an edit is done, or abandoned, focus goes back to the row, so the keyboard can carry
+    // on from where it was rather than starting again from the top of the page.
+    let stop_editing = move || {
+        editing.set(false);
+        duplicate.set(None);
+        if let Some(row) = row_ref.get_untracked() {
+            let _ = row.focus();
+        }
+    };
+    // Only keys pressed on the row itself count, not those bubbling up from its inputs and
+    // buttons, where the arrows, Enter and Delete already mean something else.
+    let on_keydown = move |ev: web_sys::KeyboardEvent| {
+        if ev.target() != ev.current_target() {
+            return;
+        }
+        let id = item.id().get_untracked();
+        match ev.key().as_str() {
+            "ArrowUp" => on_nav.run((id, Direction::Up)),
+            "ArrowDown" => on_nav.run((id, Direction::Down)),
+            "Enter" => open_editor(),
+            "Delete" => on_delete.run(id),
+            _ => return,
+        }
+        ev.prevent_default();
+    };
+
+    let overdue = Signal::derive(move || {
+        let due = item.due().get();
+        now.get()
+            .zip(due)
+            .is_some_and(|(now, due)| is_overdue(now, due))
+    });
+
+    // The input only exists once `editing` flips, so focus it as soon as its node is mounted.
+    Effect::new(move |_| {
+        if let Some(input) = input_ref.get() {
+            let _ = input.focus();
+        }
+    });
+    // Whether the row is being edited is its own business, so the context menu's Edit can only
+    // ask, see `edit_request` in `Items`.
+    Effect::new(move |_| {
+        if edit_requested.get() {
+            open_editor();
+        }
+    });
+
+    view! {
+        <div
+            class=move || {
+                let border = match drop_indicator.get() {
+                    Some(Direction::Up) => "border-t-sky-500 border-b-transparent",
+                    Some(Direction::Down) => "border-t-transparent border-b-sky-500",
+                    None => "border-transparent",
+                };
+                format!(
+                    "flex flex-col gap-2 border-y-2 {border} focus:outline-none \
+                     focus-visible:ring-2 focus-visible:ring-inset focus-visible:ring-sky-500"
+                )
+            }
+            class=("bg-red-50", overdue)
+            aria-busy=move || busy.get().to_string()
+            node_ref=row_ref
+            id=row_element_id(item.id().get_untracked())
+            tabindex=move || if tabbable.get() { "0" } else { "-1" }
+            on:focusin=move |_| on_focus_row.run(item.id().get_untracked())
+            on:keydown=on_keydown
+            // Whilst editing, the browser's own menu is left for the input, for cutting and
+            // pasting.
+            on:contextmenu=move |ev| {
+                if editing.get_untracked() {
+                    return;
+                }
+                ev.prevent_default();
+                on_context_menu.run((item.id().get_untracked(), ev.client_x(), ev.client_y()));
+            }
+            draggable="true"
+            on:dragstart=move |ev| {
+                let id = item.id().get_untracked();
+                if let Some(data) = ev.data_transfer() {
+                    let _ = data.set_data("text/plain", &id.to_string());
+                }
+                on_drag.run(Some(id));
+            }
+            on:dragend=move |_| on_drag.run(None)
+            on:dragover=move |ev| {
+                // Cancelling dragover is what tells the browser this row accepts drops.
+                ev.prevent_default();
+                on_drag_over.run(item.id().get_untracked());
+            }
+            on:drop=move |ev| {
+                ev.prevent_default();
+                let source = ev
+                    .data_transfer()
+                    .and_then(|data| data.get_data("text/plain").ok())
+                    .and_then(|id| id.parse::<u128>().ok());
+                let target = item.id().get_untracked();
+                if let Some(source) = source {
+                    on_drop.run((source, target));
+                }
+            }
+        >
+            // Whilst busy, the fieldset disables every control within it at once, so none of the
+            // row's buttons can be pressed again before the server has answered.
+            <fieldset class="flex gap-2 min-w-0" disabled=move || busy.get()>
+                <input
+                    type="checkbox"
+                    title="Select"
+                    prop:checked=move || selected.get()
+                    on:change=move |_| on_select.run(item.id().get_untracked())
+                />
+                <input
+                    type="checkbox"
+                    title="Done"
+                    class="accent-green-600"
+                    prop:checked=move || item.done().get()
+                    on:change=move |_| on_toggle.run(item.id().get_untracked())
+                />
                 <button
-                    class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded"
-                    on:click=move |_| {
-                        store.items().update(move |items| {
-                            let id = uuid::Uuid::new_v4();
-                            items.push(Item {
-                                id: id.as_u128(),
-                                value: "Value".to_string(),
-                            });
-                        });
-                    }
+                    class="text-lg leading-none"
+                    class=("text-amber-500", move || item.favorite().get())
+                    class=("text-neutral-300", move || !item.favorite().get())
+                    title=move || if item.favorite().get() { "Unfavorite" } else { "Favorite" }
+                    aria-pressed=move || item.favorite().get().to_string()
+                    on:click=move |_| on_favorite.run(item.id().get_untracked())
                 >
-                    Add
+                    "★"
                 </button>
-                <button
-                    class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded"
-                    on:click=move |_| {
-                        store.items().update(|items| {
-                            let len = items.len();
-                            if len >= 2 {
-                                let item = items.get_mut(len - 2).unwrap();
-                                item.value = "Mutated".to_string();
+                <Show
+                    when=move || editing.get()
+                    fallback=move || view! {
+                        <div
+                            class="flex-grow cursor-text"
+                            class=("line-through", move || item.done().get())
+                            class=("text-neutral-400", move || item.done().get())
+                            on:click=move |ev| {
+                                if !in_link(&ev) {
+                                    open_editor();
+                                }
                             }
-                        });
+                        >
+                            {
+                                // Logged each time this text re-renders, with the `mutation-log`
+                                // feature. Clicking Refresh only logs for rows whose value
+                                // actually changed on the server, see `merge_items`. Typing in
+                                // the search box re-renders the text of every row still shown,
+                                // as each highlights what matches, see `split_matches`.
+                                move || {
+                                    let id = item.id().get();
+                                    #[cfg(feature = "mutation-log")]
+                                    leptos::logging::log!("Rendering item {id}");
+                                    if markdown.get() {
+                                        let html = highlight.with(|query| {
+                                            item.value().with(|value| markdown::to_html(value, query))
+                                        });
+                                        return view! {
+                                            <span inner_html=html></span>
+                                            {format!(" ({id})")}
+                                        }
+                                            .into_any();
+                                    }
+                                    let parts = highlight.with(|query| {
+                                        item.value().with(|value| split_matches(value, query))
+                                    });
+                                    view! {
+                                        {parts
+                                            .into_iter()
+                                            .map(|(text, matched)| {
+                                                if matched {
+                                                    view! {
+                                                        <mark class="bg-yellow-200 rounded-sm">{text}</mark>
+                                                    }
+                                                        .into_any()
+                                                } else {
+                                                    text.into_any()
+                                                }
+                                            })
+                                            .collect_view()}
+                                        {format!(" ({id})")}
+                                    }
+                                        .into_any()
+                                }
+                            }
+                            <Show when=move || duplicated.get()>
+                                <span
+                                    class="ml-2 px-1 rounded bg-amber-100 text-amber-700 text-xs"
+                                    title="Another item has the same value"
+                                >
+                                    Duplicate
+                                </span>
+                            </Show>
+                        </div>
                     }
                 >
-                    Mutate n-1
-                </button>
+                    <div class="flex-grow flex flex-col">
+                        <input
+                            node_ref=input_ref
+                            type="text"
+                            class="border border-neutral-300 px-2 rounded"
+                            class=("border-red-600", over_limit)
+                            aria-invalid=move || over_limit().to_string()
+                            prop:value=draft.get_untracked()
+                            on:input=move |ev| {
+                                draft.set(event_target_value(&ev));
+                                let candidate = Item {
+                                    value: event_target_value(&ev),
+                                    ..item.get_untracked()
+                                };
+                                error.set(validate_item(&candidate).err());
+                                duplicate.set(check_duplicate.run((candidate.id, candidate.value)));
+                            }
+                            on:keydown=move |ev| match ev.key().as_str() {
+                                "Enter" => {
+                                    // Read everything we need from the row **before** asking for the
+                                    // write, exactly as with `on_delete`.
+                                    let id = item.id().get_untracked();
+                                    let value = event_target_value(&ev);
+                                    let candidate = Item { value, ..item.get_untracked() };
+                                    if let Err(message) = validate_item(&candidate) {
+                                        error.set(Some(message));
+                                        return;
+                                    }
+                                    let unique = check_duplicate.run((id, candidate.value.clone()));
+                                    if unique.is_some_and(|unique| unique.blocks()) {
+                                        duplicate.set(unique);
+                                        return;
+                                    }
+                                    // Truncating is left to `Items`, which does so whoever's
+                                    // saving.
+                                    if over_limit() && over_length == OverLength::Block {
+                                        return;
+                                    }
+                                    stop_editing();
+                                    on_edit.run((id, candidate.value));
+                                }
+                                "Escape" => {
+                                    error.set(None);
+                                    stop_editing();
+                                }
+                                _ => {}
+                            }
+                        />
+                        <span class="text-sm text-red-600">{ move || error.get() }</span>
+                        {move || {
+                            duplicate
+                                .get()
+                                .map(|unique| view! { <span class=unique.class()>{unique.message()}</span> })
+                        }}
+                        <span
+                            class="text-xs text-neutral-500 tabular-nums"
+                            class=("text-red-600", over_limit)
+                            aria-live="polite"
+                        >
+                            {move || match max_length {
+                                Some(max) if over_limit() => match over_length {
+                                    OverLength::Block => {
+                                        format!("{}/{max}, too long to save", length.get())
+                                    }
+                                    OverLength::Truncate => {
+                                        format!("{}/{max}, only the first {max} will be saved", length.get())
+                                    }
+                                },
+                                Some(max) => format!("{}/{max}", length.get()),
+                                None => length.get().to_string(),
+                            }}
+                        </span>
+                    </div>
+                </Show>
+                <div class="flex text-sm">
+                    {Status::ALL
+                        .into_iter()
+                        .map(|status| {
+                            view! {
+                                <button
+                                    class="px-2 py-1 border border-neutral-300 first:rounded-l last:rounded-r -ml-px first:ml-0"
+                                    class=("bg-neutral-300", move || item.status().get() == status)
+                                    on:click=move |_| on_status.run((item.id().get_untracked(), status))
+                                >
+                                    {status.label()}
+                                </button>
+                            }
+                        })
+                        .collect_view()}
+                </div>
+                <div class="flex items-center gap-1">
+                    <span
+                        class=move || {
+                            let colour = item.color().get().map_or("border border-neutral-300", |c| c.class());
+                            format!("w-3 h-3 rounded-full {colour}")
+                        }
+                    ></span>
+                    <select
+                        title="Colour"
+                        class="border border-neutral-300 text-sm rounded"
+                        prop:value=move || item.color().get().map_or("", |c| c.label())
+                        on:change=move |ev| {
+                            let value = event_target_value(&ev);
+                            let color = ColorLabel::ALL.into_iter().find(|c| c.label() == value);
+                            on_color.run((item.id().get_untracked(), color));
+                        }
+                    >
+                        <option value="">No colour</option>
+                        {ColorLabel::ALL
+                            .into_iter()
+                            .map(|color| view! { <option value=color.label()>{color.label()}</option> })
+                            .collect_view()}
+                    </select>
+                </div>
+                <div class="flex items-center gap-1">
+                    <For
+                        each=move || item.tags().get()
+                        key=|tag| tag.clone()
+                        children=move |tag| {
+                            let label = tag.clone();
+                            view! {
+                                <span class="flex items-center gap-1 bg-sky-100 text-sky-800 text-sm px-2 rounded-full">
+                                    {label}
+                                    <button
+                                        title="Remove tag"
+                                        on:click=move |_| {
+                                            let id = item.id().get_untracked();
+                                            on_remove_tag.run((id, tag.clone()));
+                                        }
+                                    >"×"</button>
+                                </span>
+                            }
+                        }
+                    />
+                    <input
+                        type="text"
+                        placeholder="Tag"
+                        class="w-16 border border-neutral-300 px-1 text-sm rounded"
+                        on:keydown=move |ev| {
+                            if ev.key() == "Enter" {
+                                let input = event_target::<web_sys::HtmlInputElement>(&ev);
+                                let id = item.id().get_untracked();
+                                on_add_tag.run((id, input.value()));
+                                input.set_value("");
+                            }
+                        }
+                    />
+                </div>
+                <div class="flex items-center gap-1">
+                    <button
+                        class="bg-neutral-200 hover:bg-neutral-300 disabled:opacity-50 px-2 py-1 rounded"
+                        disabled=move || item.quantity().get() == 0
+                        on:click=move |_| {
+                            let id = item.id().get_untracked();
+                            on_quantity.run((id, -1));
+                        }
+                    >-</button>
+                    <span class="w-8 text-center">{ move || item.quantity().get() }</span>
+                    <button
+                        class="bg-neutral-200 hover:bg-neutral-300 px-2 py-1 rounded"
+                        on:click=move |_| {
+                            let id = item.id().get_untracked();
+                            on_quantity.run((id, 1));
+                        }
+                    >+</button>
+                </div>
+                <input
+                    type="date"
+                    title="Due"
+                    class="border border-neutral-300 text-sm px-1 rounded"
+                    class=("text-red-600", overdue)
+                    prop:value=move || item.due().get().map(format_date).unwrap_or_default()
+                    on:change=move |ev| on_due.run((item.id().get_untracked(), parse_date(&event_target_value(&ev))))
+                />
+                <span class="self-center w-16 text-sm text-neutral-500 text-right">
+                    {move || {
+                        let created_at = item.created_at().get();
+                        now.get()
+                            .filter(|_| created_at != 0)
+                            .map(|now| relative_time(now, created_at))
+                    }}
+                </span>
                 <button
-                    class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded"
+                    class="bg-neutral-200 hover:bg-neutral-300 disabled:opacity-50 px-4 py-1 rounded"
+                    disabled=move || first.get()
                     on:click=move |_| {
-                        store.items().update(move |items| {
-                            if items.len() > 0 {
-                                items.remove(0);
-                            }
-                        });
+                        let id = item.id().get_untracked();
+                        on_move.run((id, Direction::Up));
+                    }
+                >Move up</button>
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 disabled:opacity-50 px-4 py-1 rounded"
+                    disabled=move || last.get()
+                    on:click=move |_| {
+                        let id = item.id().get_untracked();
+                        on_move.run((id, Direction::Down));
+                    }
+                >Move down</button>
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 px-4 py-1 rounded"
+                    on:click=move |_| {
+                        let id = item.id().get_untracked();
+                        on_duplicate.run(id);
+                    }
+                >Duplicate</button>
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 px-4 py-1 rounded"
+                    on:click=move |_| on_copy.run(item.id().get_untracked())
+                >Copy</button>
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 px-4 py-1 rounded"
+                    class=("hidden", move || item.value().with(|value| split_value(value).len() < 2))
+                    on:click=move |_| on_split.run(item.id().get_untracked())
+                >Split</button>
+                <a
+                    class="bg-neutral-200 hover:bg-neutral-300 px-4 py-1 rounded"
+                    href=move || format!("/item/{}", item.id().get())
+                >Details</a>
+                <button
+                    class="flex gap-2 items-center bg-neutral-200 hover:bg-neutral-300 disabled:opacity-50 px-4 py-1 rounded"
+                    disabled=move || deleting.get()
+                    aria-label=move || format!("Delete item: {}", item.value().get())
+                    on:click=move |_| {
+                        let id = item.id().get_untracked();
+                        on_delete.run(id);
                     }
                 >
-                    Delete 0
+                    <Show when=move || deleting.get()>
+                        <span class="w-3 h-3 border-2 border-neutral-500 border-t-transparent rounded-full animate-spin"></span>
+                    </Show>
+                    Delete
                 </button>
-            </div>
-            <div class="flex flex-col gap-4">
-                <For each=move || store.items() key=|i|i.id().get() let:item>
-                    <Item item on_delete />
-                </For>
-            </div>
-        }
+                <Show when=move || busy.get() && !deleting.get()>
+                    <span
+                        class="self-center w-3 h-3 border-2 border-neutral-500 border-t-transparent rounded-full animate-spin"
+                        title="Saving"
+                    ></span>
+                </Show>
+            </fieldset>
+            <Description
+                item
+                on_description=move |id, description| on_description.run((id, description))
+            />
+            <SubItems
+                item
+                expanded
+                on_expand=move |id| on_expand.run(id)
+                on_add_child=move |id, value| on_add_child.run((id, value))
+                on_remove_child=move |id, child| on_remove_child.run((id, child))
+            />
+        </div>
     }
+    // Erasing the row's view type keeps the For's type, and the whole of `Items` with it, small
+    // enough for the compiler to resolve.
+    .into_any()
 }
 
+/// A column header of the table view, sorting by `key` when clicked.
+///
+/// The sort is the same `sort_key` and `ascending` the sort select sets, handed down from
+/// `Items`, so the table and the select always agree, and the URL follows either. Clicking the
+/// column already sorted by flips its direction, and any other sorts by that column ascending.
 #[component]
-fn Home() -> impl IntoView {
+fn SortHeader(
+    label: &'static str,
+    key: SortKey,
+    sort_key: RwSignal<Option<SortKey>>,
+    ascending: RwSignal<bool>,
+) -> impl IntoView {
+    let active = move || sort_key.get() == Some(key);
     view! {
-        <Title text="Store Vec Demo"/>
-        <main class="grid justify-center content-center mt-[20vh]">
-            <div class="max-w-xl">
-                <p class="mb-16 [&_a]:text-sky-600 [&_a]:font-bold [&_a:hover]:underline">This demo is a reference for how to add, update and delete items from a <a href="https://doc.rust-lang.org/std/vec/struct.Vec.html">Vec</a> inside of a <a href="https://docs.rs/reactive_stores/latest/reactive_stores/struct.Store.html">Store</a> derived from a <a href="https://docs.rs/leptos/latest/leptos/prelude/struct.Resource.html">Resource</a>. { r#"It's"# } suprisingly easy to convolute the reactivity decision tree leading to impenetrable error messages.</p>
-                // Suspense component define the boundary of use for any Resource accessed
-                // within. Calling `resource.get()` outside of a Suspense throws a warning.
-                // Calling `resource.get()` within a suspense can be unwrapped.
-                //
-                // See the <Items /> component comments for more on Resources.
-                <Suspense>
-                    <Items />
-                </Suspense>
-            </div>
-        </main>
+        <th
+            class="px-2 py-1 text-left font-normal"
+            aria-sort=move || match (active(), ascending.get()) {
+                (false, _) => "none",
+                (true, true) => "ascending",
+                (true, false) => "descending",
+            }
+        >
+            <button
+                class="flex gap-1 hover:underline"
+                class=("font-bold", active)
+                on:click=move |_| {
+                    if active() {
+                        ascending.update(|ascending| *ascending = !*ascending);
+                    } else {
+                        sort_key.set(Some(key));
+                        ascending.set(true);
+                    }
+                }
+            >
+                {label}
+                <span class="w-3">
+                    {move || match (active(), ascending.get()) {
+                        (false, _) => "",
+                        (true, true) => "▲",
+                        (true, false) => "▼",
+                    }}
+                </span>
+            </button>
+        </th>
     }
 }
 
-#[derive(Debug, Clone, Store, Patch, Serialize, Deserialize)]
-pub struct Item {
-    /// An item's ID uniquely identifies each item in a keyed list
-    /// such as Leptos' For component. Use an UUID is great way to
-    /// generate random and unique ID's without centralised checking.
-    pub id: u128,
+/// A single item as a row of the table view, each field a cell.
+///
+/// This is the denser counterpart to `Item`, given the same keyed Field and passing raw IDs up
+/// through the same callbacks, so whichever view makes a change, `Items` handles it alike. Only
+/// the fields that fit a cell are editable here: the value, inline, along with ticking it off,
+/// starring it and its status.
+///
+/// The value's input is always open, committing through `on_edit` when it loses focus or Enter
+/// is pressed. As in `Item`, a value that fails `validate_item` isn't passed up, its cell being
+/// outlined in red with the reason as its title until it's fixed, or until Escape puts back the
+/// value the Store holds. So too is one `check_duplicate` blocks, though in a cell this narrow
+/// there's no room to warn of a duplicate that isn't blocked.
+#[component]
+fn ItemTableRow(
+    #[prop(into)] item: reactive_stores::Field<Item>,
+    on_delete: impl Fn(u128) + Copy + 'static,
+    on_toggle: impl Fn(u128) + Copy + 'static,
+    on_favorite: impl Fn(u128) + Copy + 'static,
+    on_status: impl Fn(u128, Status) + Copy + 'static,
+    on_edit: impl Fn(u128, String) + Copy + 'static,
+    check_duplicate: impl Fn(u128, String) -> Option<UniqueValues> + Copy + 'static,
+    #[prop(into)] now: Signal<Option<i64>>,
+) -> impl IntoView {
+    let error = RwSignal::new(None::<String>);
+    let commit = move |input: web_sys::HtmlInputElement| {
+        let value = input.value();
+        if value == item.value().get_untracked() {
+            error.set(None);
+            return;
+        }
+        let candidate = Item {
+            value: value.clone(),
+            ..item.get_untracked()
+        };
+        match validate_item(&candidate) {
+            Ok(()) => match check_duplicate(candidate.id, value.clone()) {
+                Some(unique) if unique.blocks() => error.set(Some(unique.message().to_string())),
+                _ => {
+                    error.set(None);
+                    on_edit(candidate.id, value);
+                }
+            },
+            Err(message) => error.set(Some(message)),
+        }
+    };
 
-    /// A demonstration example of some data
-    pub value: String,
+    view! {
+        <tr class="border-t border-neutral-200" class=("line-through", move || item.done().get())>
+            <td class="px-2 py-1 text-sm text-neutral-500">
+                {move || {
+                    let id = item.id().get().to_string();
+                    format!("…{}", &id[id.len().saturating_sub(6)..])
+                }}
+            </td>
+            <td class="px-2 py-1">
+                <input
+                    type="text"
+                    class="w-full border border-transparent hover:border-neutral-300 px-1 rounded"
+                    class=("border-red-600", move || error.with(Option::is_some))
+                    title=move || error.get()
+                    prop:value=move || item.value().get()
+                    on:change=move |ev| commit(event_target::<web_sys::HtmlInputElement>(&ev))
+                    on:keydown=move |ev| {
+                        let input = event_target::<web_sys::HtmlInputElement>(&ev);
+                        match ev.key().as_str() {
+                            "Enter" => commit(input),
+                            "Escape" => {
+                                input.set_value(&item.value().get_untracked());
+                                error.set(None);
+                            }
+                            _ => {}
+                        }
+                    }
+                />
+            </td>
+            <td class="px-2 py-1 text-right">{move || item.quantity().get()}</td>
+            <td class="px-2 py-1 text-center">
+                <input
+                    type="checkbox"
+                    title="Done"
+                    prop:checked=move || item.done().get()
+                    on:change=move |_| on_toggle(item.id().get_untracked())
+                />
+            </td>
+            <td class="px-2 py-1">
+                <select
+                    class="border border-neutral-300 text-sm rounded"
+                    prop:value=move || item.status().get().label()
+                    on:change=move |ev| {
+                        let label = event_target_value(&ev);
+                        if let Some(status) = Status::ALL.into_iter().find(|s| s.label() == label) {
+                            on_status(item.id().get_untracked(), status);
+                        }
+                    }
+                >
+                    {Status::ALL
+                        .into_iter()
+                        .map(|status| view! { <option value=status.label()>{status.label()}</option> })
+                        .collect_view()}
+                </select>
+            </td>
+            <td class="px-2 py-1 text-center">
+                <button
+                    title="Favorite"
+                    class=move || if item.favorite().get() { "text-amber-500" } else { "text-neutral-300" }
+                    on:click=move |_| on_favorite(item.id().get_untracked())
+                >"★"</button>
+            </td>
+            <td class="px-2 py-1 text-sm">{move || item.due().get().map(format_date)}</td>
+            <td class="px-2 py-1 text-sm text-neutral-500">
+                {move || {
+                    let created_at = item.created_at().get();
+                    now.get()
+                        .filter(|_| created_at != 0)
+                        .map(|now| relative_time(now, created_at))
+                }}
+            </td>
+            <td class="px-2 py-1">
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 px-2 py-1 rounded text-sm"
+                    aria-label=move || format!("Delete item: {}", item.value().get())
+                    on:click=move |_| on_delete(item.id().get_untracked())
+                >Delete</button>
+            </td>
+        </tr>
+    }
+    // Erased just as `Item` is, for the same reason.
+    .into_any()
 }
 
-#[derive(Debug, Clone, Store, Patch, Serialize, Deserialize)]
-pub struct Data {
-    /// It's imperative to use to a Copy type such as u128 for the
-    /// Store's Vec key. Using a String, for example, throws an
-    /// obtuse error when later iterating over the items in a Leptos
-    /// For component.
-    #[store(key: u128 = |item| item.id)]
-    items: Vec<Item>,
+/// An item's optional description, beneath its row.
+///
+/// `Item::description` is an `Option<String>`, so its Field reads and writes the whole `Option`.
+/// Whether there's a description at all is checked with `.with(Option::is_some)`, which reads it
+/// in place rather than cloning the text out, and the text itself with `.get()` once known to be
+/// there. Changes go up through `on_description` as the whole new `Option`: `Some` with the
+/// edited text, or `None` to clear it. Edits are committed when the text area loses focus, so
+/// typing doesn't record an undo step for every keystroke.
+///
+/// Both branches are within a Show, so `on_description` must be `Send + Sync`.
+#[component]
+fn Description(
+    #[prop(into)] item: reactive_stores::Field<Item>,
+    on_description: impl Fn(u128, Option<String>) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    view! {
+        <div class="ml-12 text-sm">
+            <Show
+                when=move || item.description().with(Option::is_some)
+                fallback=move || view! {
+                    <span class="text-neutral-500">No description</span>
+                    " "
+                    <button
+                        class="text-sky-600 hover:underline"
+                        on:click=move |_| on_description(item.id().get_untracked(), Some(String::new()))
+                    >Add description</button>
+                }
+            >
+                <div class="flex gap-2 items-start">
+                    <textarea
+                        class="flex-grow border border-neutral-300 px-1 rounded"
+                        rows="2"
+                        prop:value=move || item.description().get().unwrap_or_default()
+                        on:change=move |ev| {
+                            let id = item.id().get_untracked();
+                            on_description(id, Some(event_target_value(&ev)));
+                        }
+                    ></textarea>
+                    <button
+                        class="text-sky-600 hover:underline"
+                        on:click=move |_| on_description(item.id().get_untracked(), None)
+                    >Clear</button>
+                </div>
+            </Show>
+        </div>
+    }
 }
 
-#[server]
-pub async fn get_items() -> Result<Vec<Item>, ServerFnError> {
-    return Ok(vec![
-        Item {
-            id: uuid::Uuid::new_v4().as_u128(),
-            value: "great".to_string(),
-        },
-        Item {
-            id: uuid::Uuid::new_v4().as_u128(),
-            value: "amasing".to_string(),
-        },
-    ]);
+/// The sub-items nested within an item, listed beneath its row.
+///
+/// Sub-items are keyed by their own ID within `Item::children`, so this is the same pattern as
+/// `Items` one level down. The inner For iterates IDs, each child's Field is looked up by its key
+/// within the parent's `children` Field, and changes go back up as raw IDs. `on_add_child` hands
+/// back the parent's ID and the new value, `on_remove_child` the parent's and the child's IDs.
+///
+/// Whether they're shown is `expanded`, handed down from `Items` which keeps it apart from the
+/// item itself, and clicking the toggle asks for it to flip through `on_expand`. Everything
+/// beneath the toggle is within a Show, so both child callbacks must be `Send + Sync`.
+#[component]
+fn SubItems(
+    #[prop(into)] item: reactive_stores::Field<Item>,
+    #[prop(into)] expanded: Signal<bool>,
+    on_expand: impl Fn(u128) + Copy + 'static,
+    on_add_child: impl Fn(u128, String) + Copy + Send + Sync + 'static,
+    on_remove_child: impl Fn(u128, u128) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let ids = Memo::new(move |_| {
+        item.children()
+            .with(|children| children.iter().map(|child| child.id).collect::<Vec<_>>())
+    });
+
+    view! {
+        <div class="ml-12 text-sm">
+            <button
+                class="text-neutral-500 hover:underline"
+                on:click=move |_| on_expand(item.id().get_untracked())
+            >
+                {move || {
+                    let arrow = if expanded.get() { "▾" } else { "▸" };
+                    format!("{arrow} {} sub-items", ids.with(Vec::len))
+                }}
+            </button>
+            <Show when=move || expanded.get()>
+                <ul class="flex flex-col gap-1 mt-1">
+                    <For
+                        each=move || ids.get()
+                        key=|id| *id
+                        children=move |child_id| {
+                            let child = AtKeyed::new(item.children(), child_id);
+                            view! {
+                                <li class="flex gap-2">
+                                    <span class="flex-grow">{ move || child.value().get() }</span>
+                                    <button
+                                        class="text-sky-600 hover:underline"
+                                        on:click=move |_| {
+                                            let id = item.id().get_untracked();
+                                            on_remove_child(id, child_id);
+                                        }
+                                    >Remove</button>
+                                </li>
+                            }
+                        }
+                    />
+                </ul>
+                <input
+                    type="text"
+                    placeholder="Add a sub-item"
+                    class="mt-1 border border-neutral-300 px-1 rounded"
+                    on:keydown=move |ev| {
+                        if ev.key() == "Enter" {
+                            let input = event_target::<web_sys::HtmlInputElement>(&ev);
+                            let id = item.id().get_untracked();
+                            on_add_child(id, input.value());
+                            input.set_value("");
+                        }
+                    }
+                />
+            </Show>
+        </div>
+    }
+}
+
+/// A form for adding a new item, handing the typed value up through `on_add`.
+///
+/// Enter submits. Once a valid value has been added the input is cleared, and focus moves on to
+/// the new item's row, see `Items`. Whilst `limit` holds a hint, such as the list being full,
+/// the hint is shown and nothing can be added.
+///
+/// Given `check_duplicate`, the value is checked against the list's as it's typed, and should
+/// another item have it, whatever `UniqueValues` that answers with says so beneath the input,
+/// `Block` refusing to add it too. It's a `Callback`, rather than an `impl Fn`, so it can be
+/// left out, as `StoreList` does.
+#[component]
+fn AddItem(
+    on_add: impl Fn(String) + Copy + 'static,
+    #[prop(into, optional)] limit: Signal<Option<String>>,
+    #[prop(optional)] check_duplicate: Option<Callback<String, Option<UniqueValues>>>,
+) -> impl IntoView {
+    let value = RwSignal::new(String::new());
+    let error = RwSignal::new(None::<String>);
+    let full = move || limit.with(Option::is_some);
+    let duplicate = Memo::new(move |_| check_duplicate.and_then(|check| check.run(value.get())));
+
+    let candidate = move || Item {
+        value: value.get_untracked(),
+        ..Default::default()
+    };
+
+    view! {
+        <form
+            class="flex gap-2 mb-4"
+            on:submit=move |ev| {
+                ev.prevent_default();
+                if full() {
+                    return;
+                }
+                if let Err(message) = validate_item(&candidate()) {
+                    error.set(Some(message));
+                    return;
+                }
+                if duplicate.get_untracked().is_some_and(|unique| unique.blocks()) {
+                    return;
+                }
+                on_add(value.get_untracked());
+                value.set(String::new());
+            }
+        >
+            <div class="flex-grow flex flex-col">
+                <input
+                    type="text"
+                    placeholder="New item"
+                    class="border border-neutral-300 px-2 py-1 rounded"
+                    prop:value=move || value.get()
+                    on:input=move |ev| {
+                        value.set(event_target_value(&ev));
+                        if error.get_untracked().is_some() {
+                            error.set(validate_item(&candidate()).err());
+                        }
+                    }
+                />
+                <span class="text-sm text-red-600">{ move || error.get() }</span>
+                {move || {
+                    duplicate
+                        .get()
+                        .map(|unique| view! { <span class=unique.class()>{unique.message()}</span> })
+                }}
+                <span class="text-sm text-neutral-500">{ move || limit.get() }</span>
+            </div>
+            <button
+                type="submit"
+                class="bg-neutral-200 hover:bg-neutral-300 disabled:opacity-50 px-4 py-2 rounded self-start"
+                disabled=full
+            >
+                Add
+            </button>
+        </form>
+    }
+}
+
+/// A textarea for adding many items at once, one per line, such as a list pasted from
+/// elsewhere.
+///
+/// Lines are trimmed, and blank ones skipped, so how many items will be added is known before
+/// anything is, and shown beneath. Adding passes the lines up through `on_add_lines` as raw
+/// values, leaving `Items` to give each its ID, and then clears the textarea. Should `room` say
+/// fewer will fit, the count says so too, `Items` dropping the rest.
+#[component]
+fn AddLines(
+    on_add_lines: impl Fn(Vec<String>) + Copy + 'static,
+    #[prop(into, optional)] room: Signal<Option<usize>>,
+) -> impl IntoView {
+    let text = RwSignal::new(String::new());
+    let lines = Memo::new(move |_| {
+        text.with(|text| {
+            text.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+    });
+
+    view! {
+        <details class="mb-4">
+            <summary class="cursor-pointer text-neutral-500">Paste lines</summary>
+            <div class="flex flex-col gap-2 mt-2">
+                <textarea
+                    class="border border-neutral-300 px-2 py-1 rounded"
+                    rows="5"
+                    placeholder="One item per line"
+                    prop:value=move || text.get()
+                    on:input=move |ev| text.set(event_target_value(&ev))
+                ></textarea>
+                <div class="flex gap-2 items-center">
+                    <button
+                        class="bg-neutral-200 hover:bg-neutral-300 disabled:opacity-50 px-4 py-2 rounded"
+                        disabled=move || lines.with(Vec::is_empty) || room.get() == Some(0)
+                        on:click=move |_| {
+                            on_add_lines(lines.get_untracked());
+                            text.set(String::new());
+                        }
+                    >
+                        Add
+                    </button>
+                    <span class="text-sm text-neutral-500">
+                        {move || match (lines.with(Vec::len), room.get()) {
+                            (0, _) => String::new(),
+                            (count, Some(0)) => format!("The list is full, so none of {count} will be added"),
+                            (count, Some(room)) if count > room => {
+                                format!("Will add {room} of {count} items, as no more fit")
+                            }
+                            (1, _) => "Will add 1 item".to_string(),
+                            (count, _) => format!("Will add {count} items"),
+                        }}
+                    </span>
+                </div>
+            </div>
+        </details>
+    }
+}
+
+/// `history_depth` caps how many mutations can be undone, and `max_items`, if given, how many
+/// items the list can hold. `rules` decides whether an item may be added or edited to a value
+/// another already has, and how long an edited value may be, see `ValueRules`.
+#[component]
+fn Items(
+    #[prop(default = 50)] history_depth: usize,
+    #[prop(default = 30)] poll_secs: u64,
+    #[prop(optional)] max_items: Option<usize>,
+    #[prop(optional)] rules: ValueRules,
+) -> impl IntoView {
+    let ValueRules {
+        unique_values,
+        max_length,
+        over_length,
+    } = rules;
+    let store = expect_context::<Store<Data>>();
+    let seed = expect_context::<StoreSeed>();
+    let toasts = use_toasts();
+    let StorageUsage(usage) = expect_context::<StorageUsage>();
+    let preferences = expect_context::<Store<Preferences>>();
+
+    move || {
+        // `wait` is `None` until the Resource resolves, which it needn't have on the first run,
+        // as Suspense builds its children once before deciding whether to show the fallback.
+        // Nothing's rendered until then, and Suspense reruns this once it has resolved.
+        let Some(seeded) = seed.wait() else {
+            return Ok(().into_any());
+        };
+        seeded?;
+
+        // Every mutation below first records a snapshot of `Data`, which is cheap to take as it
+        // derives Clone. Restoring a snapshot writes through `store.items()` rather than the
+        // Store as a whole, as it's the keyed field's write that refreshes the ID to index
+        // mapping the For below relies upon.
+        let history = RwSignal::new(History::<Data>::new(history_depth));
+        let record = move || {
+            let snapshot = snapshot(store);
+            history.write().record(snapshot.clone());
+            snapshot
+        };
+
+        // With `max_items` set, anything that would add items first checks there's `room`.
+        // Those adding one at a time, or a known few, such as duplicating or splitting, are
+        // refused outright when there isn't, through `full`. Those adding many at once, such
+        // as pasting or importing, are cut short with `fit`, which keeps as many as there's
+        // room for, in order, and says how many were dropped rather than leaving it to be
+        // noticed. Undo, redo and resetting to the server are left be, as they only go back to
+        // lists there's already been.
+        let room =
+            move || max_items.map(|max| max.saturating_sub(store.items().with_untracked(Vec::len)));
+        let full = move |adding: usize| {
+            let full = room().is_some_and(|room| room < adding);
+            if full {
+                toasts.push_toast(
+                    format!(
+                        "The list can only hold {} items",
+                        max_items.unwrap_or_default()
+                    ),
+                    ToastKind::Error,
+                );
+            }
+            full
+        };
+        let fit = move |mut items: Vec<Item>, room: Option<usize>| {
+            if let (Some(max), Some(room)) = (max_items, room) {
+                if items.len() > room {
+                    toasts.push_toast(
+                        format!(
+                            "Only {room} of {} items were added, as the list can only hold {max}",
+                            items.len()
+                        ),
+                        ToastKind::Info,
+                    );
+                    items.truncate(room);
+                }
+            }
+            items
+        };
+
+        // With `unique_values` on, what's typed is looked up in `value_owners`, mapping each
+        // value, as `value_key` compares them, to the IDs of the items holding it. Being a memo,
+        // it's rebuilt once per change to the list, however many keystrokes check against it in
+        // between, and `duplicate_ids` follows it, only notifying the rows' badges when which
+        // items share a value has actually changed. `check_duplicate` leaves out the item being
+        // edited, as it's no duplicate of itself, and answers with `unique_values` when another
+        // has the value. It's read tracked, so the forms' warnings follow the list as it changes
+        // beneath them. With it off, the memos are left empty and nothing is checked.
+        let value_owners = Memo::new(move |_| {
+            let mut owners = HashMap::<String, Vec<u128>>::new();
+            if unique_values != UniqueValues::Off {
+                store.items().with(|items| {
+                    for item in items {
+                        owners
+                            .entry(value_key(&item.value))
+                            .or_default()
+                            .push(item.id);
+                    }
+                });
+            }
+            owners
+        });
+        let duplicate_ids = Memo::new(move |_| {
+            value_owners.with(|owners| {
+                owners
+                    .values()
+                    .filter(|ids| ids.len() > 1)
+                    .flatten()
+                    .copied()
+                    .collect::<HashSet<_>>()
+            })
+        });
+        let check_duplicate = move |id: Option<u128>, value: String| {
+            let taken = value_owners.with(|owners| {
+                owners
+                    .get(&value_key(&value))
+                    .is_some_and(|ids| ids.iter().any(|other| Some(*other) != id))
+            });
+            (unique_values != UniqueValues::Off && taken).then_some(unique_values)
+        };
+        // The forms refuse a value `Block` won't have before it's passed up, but these check
+        // it again, as the shortcuts passing values straight in haven't.
+        let blocked = move |id: Option<u128>, value: &str| {
+            let blocked =
+                check_duplicate(id, value.to_string()).is_some_and(|unique| unique.blocks());
+            if blocked {
+                toasts.push_toast(UniqueValues::Block.message(), ToastKind::Error);
+            }
+            blocked
+        };
+
+        // Mutations are optimistic. Each applies to the local Store immediately, and only then
+        // is the resulting list sent to the server. Should saving fail, the Store rolls back to
+        // the snapshot taken before that mutation and the error is shown in the toolbar.
+        //
+        // Only the changes since the last successful save are sent, as a `SaveDiff`, compared
+        // against `last_saved`. Until there's been one there's nothing to compare against, so
+        // the first save sends the whole list. So does any save `save_diff` refuses, in case
+        // the server has lost track of the list, though not one that couldn't reach it at all.
+        // Adds, edits and deletes also reach the server through `pending` below without
+        // passing through here, so a diff may repeat some of them, which `save_diff` shrugs
+        // off.
+        let last_saved = StoredValue::new(None::<Vec<Item>>);
+
+        // The list as the server is known to have it, for telling whether there are local
+        // changes that haven't reached it, see `dirty` below. It starts as the list was loaded,
+        // and follows every answer from the server: a successful save, each mutation from
+        // `pending` the server accepts, a refresh and a reset.
+        let server_items = RwSignal::new(store.items().get_untracked());
+        let save_action = Action::new(move |(before, after): &(Data, Data)| {
+            let before = before.clone();
+            let items = after.items.clone();
+            let last = last_saved.get_value();
+            async move {
+                let diffed = match &last {
+                    Some(last) => {
+                        let diff = SaveDiff::between(last, &items);
+                        if diff.is_empty() {
+                            Ok(())
+                        } else {
+                            save_diff(diff).await
+                        }
+                    }
+                    None => Err(ServerFnError::new("Nothing saved yet")),
+                };
+                // Without a connection there's no point trying the whole list either.
+                let result = match diffed {
+                    Err(error @ ServerFnError::Request(_)) => Err(error),
+                    Err(_) => save_items(items.clone()).await,
+                    Ok(()) => Ok(()),
+                };
+                result
+                    .map(|()| {
+                        server_items.try_set(items.clone());
+                        last_saved.try_update_value(|last| *last = Some(items));
+                    })
+                    .map_err(|error| (before, error))
+            }
+        });
+
+        // Sending is debounced, so a burst of mutations, such as rapid edits or clicks of a
+        // stepper, results in a single `save_items` once there's been 500ms without another.
+        // Each mutation bumps `unsaved`, restarting the wait. Only the snapshot from before the
+        // first of them is kept, so a failure rolls the whole burst back. Leaving the page
+        // mid-wait still sends it, see `debounce`.
+        //
+        // Offline, there's no point sending it at all. The snapshot is held instead, `held`
+        // counting it as a change pending in the toolbar, and sent once the browser is back
+        // online, see `pending` below.
+        let online = RwSignal::new(true);
+        let unsaved = RwSignal::new(0_u64);
+        let unsaved_before = StoredValue::new(None::<Data>);
+        let held = RwSignal::new(false);
+        let save = move |label: &'static str, before: Data| {
+            log_mutation(label, &before, store);
+            unsaved_before.update_value(|unsaved| {
+                unsaved.get_or_insert(before);
+            });
+            unsaved.update(|unsaved| *unsaved += 1);
+        };
+        let send_save = move || {
+            held.try_set(false);
+            if let Some(before) = unsaved_before.try_update_value(Option::take).flatten() {
+                save_action.dispatch((before, snapshot(store)));
+            }
+        };
+        let save_waiting = debounce(unsaved.into(), Duration::from_millis(500), move |_: u64| {
+            if online.try_get_untracked().unwrap_or(true) {
+                send_save();
+            } else {
+                held.try_set(true);
+            }
+        });
+        // How each save turned out is told with a toast, a failure also rolling back.
+        Effect::new(move |_| match save_action.value().get() {
+            Some(Ok(())) => {
+                toasts.push_toast("Saved", ToastKind::Success);
+            }
+            Some(Err((before, error))) => {
+                restore(store, before);
+                toasts.push_toast(format!("Saving failed. {error}"), ToastKind::Error);
+            }
+            None => {}
+        });
+        let save_status =
+            move || (save_waiting.get() || save_action.pending().get()).then_some("Saving…");
+
+        // Refreshing fetches the items afresh and merges them into the Store, see `pending`
+        // below for how.
+        //
+        // Refreshes can overlap, such as a click of Refresh whilst an auto-refresh is still in
+        // flight, and their answers can arrive in either order. An Action keeps whichever
+        // arrives last, so each fetch is numbered as it's dispatched, and only the latest
+        // fetch's answer is applied, see `Generations`.
+        let refreshes = StoredValue::new(Generations::default());
+        let resets = StoredValue::new(Generations::default());
+        let issue = |generations: StoredValue<Generations>| {
+            generations
+                .try_update_value(Generations::issue)
+                .unwrap_or_default()
+        };
+        let is_latest = |generations: StoredValue<Generations>, generation: u64| {
+            generations
+                .try_with_value(|generations| generations.is_latest(generation))
+                .unwrap_or(false)
+        };
+        let refresh_action = Action::new(move |_: &()| {
+            let generation = issue(refreshes);
+            async move { (generation, get_items().await) }
+        });
+
+        // Resetting throws away every local change, keeping only what's on the server, see
+        // `reset` below. Resets are numbered likewise. Each also supersedes any refresh already
+        // in flight, lest a slow one land after the reset and bring back a list from before it.
+        // A refresh made after a reset doesn't supersede it, though, the reset having been
+        // asked for explicitly.
+        let reset_action = Action::new(move |_: &()| {
+            let generation = issue(resets);
+            issue(refreshes);
+            async move { (generation, get_items().await) }
+        });
+
+        // Undoing restores a snapshot through `restore`, so undoing an edit only re-renders the
+        // fields that edit changed.
+        let undo = move || {
+            let before = snapshot(store);
+            let snapshot = history.write().undo(before.clone());
+            if let Some(snapshot) = snapshot {
+                restore(store, snapshot);
+                save("undo", before);
+            }
+        };
+        let redo = move || {
+            let before = snapshot(store);
+            let snapshot = history.write().redo(before.clone());
+            if let Some(snapshot) = snapshot {
+                restore(store, snapshot);
+                save("redo", before);
+            }
+        };
+
+        // Ctrl+Z undoes and Ctrl+Shift+Z redoes, except whilst typing in a field where the
+        // browser's own text undo should win. The listener lives on the window, so it must be
+        // removed when this closure's owner is cleaned up.
+        let handle = window_event_listener(ev::keydown, move |ev| {
+            if !(ev.ctrl_key() || ev.meta_key()) || !ev.key().eq_ignore_ascii_case("z") {
+                return;
+            }
+            if typing() {
+                return;
+            }
+            ev.prevent_default();
+            if ev.shift_key() {
+                redo();
+            } else {
+                undo();
+            }
+        });
+        on_cleanup(move || handle.remove());
+
+        // Most pitfalls were encountered deleting a specific item:
+        //
+        // 1. Read the item ID **before** writing the Vec from which it was reactively derived.
+        //
+        // ❌ store.items().update(|items| {
+        //     let position = items.iter().position(|i| i.id == item.id().get()).unwrap();
+        //     items.remove(position);
+        // });
+        //
+        // ✅ let id = item.id().get();
+        // store.items().update(|items| {
+        //     let position = items.iter().position(|i| i.id == id).unwrap();
+        //     items.remove(position);
+        // });
+        //
+        // 2. Item ID must be a copy and not a reference to survive it's disposal by
+        // the subsequent Signal write via, say, `store.items().update(...)`.
+        //
+        // ❌ let id = items.id().read();
+        // let id: &u128 = id.as_borrowed();
+        // store.items().update(...);
+        //
+        // ✅ let id = items.id().get();
+        // store.items().update(...);
+        //
+        // `remove_by_id` follows both rules, so deletes go through it rather than writing the
+        // update by hand.
+        //
+        // Once confirmed, see `confirming` below, a delete applies straight away, moving the
+        // item into the trash, and raises its own toast offering to put the item back for a few
+        // seconds. `trash_by_id` hands back the index it was removed from, so undoing can splice
+        // it back where it was. Other rows may have come and gone since, so `restore_by_id`
+        // clamps the index to the end of the list.
+        //
+        // Every other way of deleting, short of emptying the trash, moves items into the trash
+        // too. Those are all undoable as well, as `record` snapshots the trash along with the
+        // list.
+        let restore_item = move |id: u128, index: Option<usize>| {
+            if full(1) {
+                return;
+            }
+            let before = record();
+            if restore_by_id(store, id, index) {
+                save("restore", before);
+            }
+        };
+
+        // Adding, editing and deleting an item only send that one item, each to its own server
+        // fn, rather than the whole list. They go by way of `pending`, a queue of `Mutation`s,
+        // so that those made whilst offline aren't lost.
+        //
+        // Like every other mutation, each applies to the Store straight away and is only then
+        // queued. The queue is sent in order, one mutation at a time, for as long as the browser
+        // is online, and each is removed once the server has answered. A request that never
+        // reached the server, a `ServerFnError::Request`, leaves it and everything after it
+        // queued until the `online` event says it's worth trying again. Any other error is the
+        // server's answer, so that mutation is dropped and undone, see `settle`.
+        //
+        // The queue is persisted to localStorage, as the Store is, so closing the tab whilst
+        // offline loses nothing. Once hydrated the queue is read back and sending resumes. Only
+        // once it's empty is a held `save` sent, so the whole list can't overtake, say, a delete
+        // that was made before it.
+        let pending = RwSignal::new(Vec::<Mutation>::new());
+        let flushing = StoredValue::new(false);
+
+        // The IDs of the items with a mutation in flight, sent and not yet answered, for their
+        // rows to show they're busy and refuse to be pressed meanwhile. Each is added just
+        // before its mutation is sent and removed once the server has answered, whether it
+        // succeeded, failed or was never reached. Those merely queued, say whilst offline,
+        // aren't counted, as they could be waiting indefinitely. A queued delete still disables
+        // its Delete button, see `deleting` below.
+        let pending_ids = RwSignal::new(HashSet::<u128>::new());
+
+        // The Store and the toasts outlive `Items`, so an answer is still handled if `Items` has
+        // gone by the time it arrives.
+        let settle = move |mutation: Mutation, result: Result<Option<Item>, ServerFnError>| {
+            // Whatever the server accepted it now has, so that's applied to `server_items`.
+            match (&mutation, &result) {
+                (Mutation::Add(item), Ok(_)) => {
+                    server_items.try_update(|items| items.push(item.clone()));
+                }
+                (Mutation::Update(item), Ok(saved)) => {
+                    let saved = saved.clone().unwrap_or_else(|| item.clone());
+                    server_items.try_update(|items| {
+                        if let Some(slot) = items.iter_mut().find(|i| i.id == saved.id) {
+                            *slot = saved;
+                        }
+                    });
+                }
+                (Mutation::Delete(id), Ok(_)) => {
+                    server_items.try_update(|items| items.retain(|i| i.id != *id));
+                }
+                (_, Err(_)) => {}
+            }
+            match (mutation, result) {
+                // The server has the final say over an edit's value, which it may have
+                // normalized. It's patched in through the item's `value` Field, so only that
+                // row's text re-renders, and only if the server changed anything. An answer is
+                // stale if the item has been mutated again since, the newer mutation still
+                // being queued, or if the item has gone.
+                (Mutation::Update(item), Ok(Some(saved))) => {
+                    let stale = pending
+                        .try_with_untracked(|pending| pending.iter().any(|m| m.id() == item.id))
+                        .unwrap_or(false);
+                    let present = store
+                        .items()
+                        .with_untracked(|items| items.iter().any(|i| i.id == item.id));
+                    if stale || !present {
+                        return;
+                    }
+                    let field = AtKeyed::new(store.items(), item.id).value();
+                    if field.get_untracked() != saved.value {
+                        field.set(saved.value);
+                    }
+                }
+                (_, Ok(_)) => {}
+                (Mutation::Add(item), Err(error)) => {
+                    remove_by_id(store, item.id);
+                    toasts.push_toast(format!("Adding failed. {error}"), ToastKind::Error);
+                }
+                (Mutation::Update(_), Err(error)) => {
+                    toasts.push_toast(format!("Saving failed. {error}"), ToastKind::Error);
+                }
+                // Deleting failed, so the item comes back out of the trash, at the end of the
+                // list as where it was is long forgotten.
+                (Mutation::Delete(id), Err(error)) => {
+                    restore_by_id(store, id, None);
+                    toasts.push_toast(format!("Deleting failed. {error}"), ToastKind::Error);
+                }
+            }
+        };
+
+        let flush = move || {
+            if flushing.get_value() || !online.get_untracked() {
+                return;
+            }
+            flushing.set_value(true);
+            leptos::task::spawn_local(async move {
+                loop {
+                    // Should `Items` go mid-flush the queue goes with it, ending the loop.
+                    // Whatever's left is in localStorage, to be sent on the next visit.
+                    let Some(next) = pending.try_with_untracked(|pending| pending.first().cloned())
+                    else {
+                        return;
+                    };
+                    let Some(mutation) = next else {
+                        break;
+                    };
+                    let id = mutation.id();
+                    pending_ids.try_update(|ids| ids.insert(id));
+                    let result = match mutation.clone() {
+                        Mutation::Add(item) => add_item(item).await.map(|()| None),
+                        Mutation::Update(item) => update_item(item).await.map(Some),
+                        Mutation::Delete(id) => delete_item(id).await.map(|()| None),
+                    };
+                    pending_ids.try_update(|ids| ids.remove(&id));
+                    if let Err(ServerFnError::Request(_)) = result {
+                        flushing.try_set_value(false);
+                        return;
+                    }
+                    pending.try_update(|pending| pending.remove(0));
+                    settle(mutation, result);
+                }
+                flushing.try_set_value(false);
+                if held.try_get_untracked() == Some(true) {
+                    send_save();
+                }
+            });
+        };
+        let send = move |mutation: Mutation| {
+            pending.update(|pending| pending.push(mutation));
+            flush();
+        };
+
+        // Reading the queue back happens in an Effect, as localStorage only exists in the
+        // browser, and only on its first run. It then persists the queue whenever it changes.
+        Effect::new(move |loaded: Option<()>| {
+            if loaded.is_none() {
+                online.set(storage::online());
+                if let Some(queue) = storage::load_pending() {
+                    pending.set(queue);
+                }
+                flush();
+            }
+            pending.with(|pending| storage::save_pending(pending));
+        });
+
+        // Like the keyboard shortcuts, these listeners live on the window and are removed when
+        // this closure's owner is cleaned up.
+        let handle = window_event_listener(ev::online, move |_| {
+            online.set(true);
+            flush();
+        });
+        on_cleanup(move || handle.remove());
+        let handle = window_event_listener(ev::offline, move |_| online.set(false));
+        on_cleanup(move || handle.remove());
+        let pending_count = move || pending.with(Vec::len) + usize::from(held.get());
+
+        // The list is dirty whilst it differs from what the server is known to have, or there's
+        // anything still waiting to be sent to it. Comparing the lists means comparing every
+        // item, on every change, which is fine for a list of this size. A much larger one would
+        // want a count of unsaved changes instead, at the cost of never noticing that a change
+        // has been undone back to what was saved.
+        let dirty = Memo::new(move |_| {
+            pending_count() > 0
+                || save_waiting.get()
+                || store
+                    .items()
+                    .with(|items| server_items.with(|saved| items != saved))
+        });
+
+        // Leaving whilst dirty asks first. Closing the tab or reloading is caught by
+        // `beforeunload`, where all the browser allows is its own generic prompt.
+        //
+        // The router has no navigation guards of its own, so following a link within the app is
+        // caught before the router sees it. The router handles link clicks in a listener on the
+        // window, and a click bubbles through the document first, so a listener there can
+        // prevent the default and the router leaves the click alone. The Store outlives the
+        // route, so it isn't the changes themselves that would be lost, but the undo history,
+        // and any save held whilst offline, both of which go with `Items`. Links that stay on
+        // this page, only changing the query, such as the filters, are let through, as are those
+        // leaving the app, `beforeunload` asking about those. Going back or forward can't be
+        // caught this way, `popstate` firing only once the URL has already changed.
+        let handle = window_event_listener(ev::beforeunload, move |ev| {
+            if dirty.try_get_untracked().unwrap_or(false) {
+                ev.prevent_default();
+                ev.set_return_value("");
+            }
+        });
+        on_cleanup(move || handle.remove());
+        Effect::new(move |_| {
+            let callback = Closure::<dyn Fn(web_sys::MouseEvent)>::new(
+                move |ev: web_sys::MouseEvent| {
+                    if ev.default_prevented() || !dirty.try_get_untracked().unwrap_or(false) {
+                        return;
+                    }
+                    let Some(anchor) = ev
+                        .target()
+                        .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+                        .and_then(|element| element.closest("a[href]").ok().flatten())
+                        .and_then(|element| element.dyn_into::<web_sys::HtmlAnchorElement>().ok())
+                    else {
+                        return;
+                    };
+                    // A link opening in a new tab, as those in a Markdown value do, leaves
+                    // this page be.
+                    let location = window().location();
+                    if anchor.origin() != location.origin().unwrap_or_default()
+                        || anchor.pathname() == location.pathname().unwrap_or_default()
+                        || anchor.target() == "_blank"
+                    {
+                        return;
+                    }
+                    let leave = window()
+                    .confirm_with_message(
+                        "You have unsaved changes, and leaving loses the undo history. Leave anyway?",
+                    )
+                    .unwrap_or(true);
+                    if !leave {
+                        ev.prevent_default();
+                    }
+                },
+            );
+            let _ = document()
+                .add_event_listener_with_callback("click", callback.as_ref().unchecked_ref());
+            let callback = StoredValue::new_local(callback);
+            on_cleanup(move || {
+                callback.try_with_value(|callback| {
+                    let _ = document().remove_event_listener_with_callback(
+                        "click",
+                        callback.as_ref().unchecked_ref(),
+                    );
+                });
+            });
+        });
+
+        // Resetting replaces the list with the server's, through `restore`, so where the IDs
+        // are unchanged only the fields that differ are patched, and rows nobody touched don't
+        // re-render. Anything not yet sent is dropped. The one mutation from `pending` that may
+        // be in flight is left for `flush` to finish with, as it can't be called back.
+        //
+        // It isn't recorded, and clears the history too, as the point is to be rid of the
+        // local changes. Undoing back past a reset would only bring them back. The trash stays
+        // as it is, being local to begin with.
+        Effect::new(move |_| {
+            let Some((generation, Ok(items))) = reset_action.value().get() else {
+                return;
+            };
+            if !is_latest(resets, generation) {
+                return;
+            }
+            unsaved_before.set_value(None);
+            held.set(false);
+            pending.update(|pending| pending.truncate(usize::from(flushing.get_value())));
+            history.write().clear();
+            let trash = store.trash().get_untracked();
+            log_mutation("reset to server", &snapshot(store), store);
+            server_items.set(items.clone());
+            last_saved.set_value(Some(items.clone()));
+            restore(store, Data { items, trash });
+            toasts.push_toast("Reset to the server's list", ToastKind::Info);
+        });
+
+        // A refresh is merged into the Store, rather than reloading the page or replacing the
+        // list wholesale. Only the fields that differ from what's on the server notify, so rows
+        // nobody else has touched don't re-render. The merge is server state arriving, not a
+        // local mutation, so it isn't recorded for undo.
+        //
+        // What's on the server is only as new as what's been sent to it, and merging would
+        // otherwise clobber any local change yet to arrive there. So an item with a mutation
+        // still in `pending` is left out of the merge, as its queued version is the newer. And
+        // whilst the whole list is waiting to be saved, or is being saved, the refresh isn't
+        // merged at all, as any item may have changed. The next refresh will pick it up.
+        Effect::new(move |_| {
+            let Some((generation, Ok(items))) = refresh_action.value().get() else {
+                return;
+            };
+            if !is_latest(refreshes, generation) {
+                return;
+            }
+            server_items.set(items.clone());
+            if unsaved_before.with_value(Option::is_some) || save_action.pending().get_untracked() {
+                return;
+            }
+            let sending = pending
+                .with_untracked(|pending| pending.iter().map(Mutation::id).collect::<HashSet<_>>());
+            merge_items(
+                store,
+                items
+                    .into_iter()
+                    .filter(|item| !sending.contains(&item.id))
+                    .collect(),
+            );
+        });
+
+        // Auto-refreshing refreshes every `poll_secs` seconds whilst it's turned on, which it
+        // isn't to begin with. Each tick is a refresh like any other, so it's merged as above.
+        // Turning it off, or leaving the page, reruns or disposes the Effect, whose cleanup
+        // clears the interval. A tick is skipped should the last refresh still be going.
+        let polling = RwSignal::new(false);
+        Effect::new(move |_| {
+            if !polling.get() {
+                return;
+            }
+            let handle = set_interval_with_handle(
+                move || {
+                    if !refresh_action.pending().get_untracked() {
+                        refresh_action.dispatch(());
+                    }
+                },
+                Duration::from_secs(poll_secs),
+            )
+            .ok();
+            on_cleanup(move || {
+                if let Some(handle) = handle {
+                    handle.clear();
+                }
+            });
+        });
+
+        // The item is moved to the trash optimistically, before the server has answered, and
+        // put back should it fail. Until then its delete is queued, so were the row to come
+        // back in the meantime, through the toast's Undo say, its Delete button shows a spinner
+        // and can't be pressed again.
+        let deleting = move |id: u128| {
+            pending.with(|pending| {
+                pending
+                    .iter()
+                    .any(|mutation| matches!(mutation, Mutation::Delete(i) if *i == id))
+            })
+        };
+        // Only recorded once there's been something to trash, so a stale ID doesn't leave an
+        // undo step that changes nothing.
+        let delete = move |id: u128| {
+            let before = snapshot(store);
+            let Some(index) = trash_by_id(store, id) else {
+                return;
+            };
+            history.write().record(before.clone());
+            log_mutation("delete", &before, store);
+            send(Mutation::Delete(id));
+            let value = store.trash().with_untracked(|trash| trash[0].value.clone());
+            toasts.push_toast_with_action(
+                format!("Moved \"{value}\" to the trash"),
+                ToastKind::Info,
+                "Undo",
+                move || restore_item(id, Some(index)),
+            );
+        };
+
+        // Deleting forever and emptying the trash don't touch the list, so there's nothing to
+        // send to the server, but they're still recorded so they can be undone.
+        let purge = move |id: u128| {
+            record();
+            store
+                .trash()
+                .update(|trash| trash.retain(|item| item.id != id));
+        };
+        let empty_trash = move || {
+            record();
+            store.trash().update(|trash| trash.clear());
+        };
+
+        // New items are given their ID here, next to the Store, rather than by the form.
+        //
+        // A new item is opened for editing straight away, saving a click. Focusing it isn't as
+        // simple as it sounds, as the handler runs before the row exists:
+        //
+        // ❌ store.items().update(|items| items.push(item));
+        //    document().get_element_by_id(...).focus(); // Not rendered yet
+        //
+        // The push only notifies the For, which creates the row when effects next run, after this
+        // handler has returned. So the new ID is left in `edit_on_mount`, which the For's
+        // children check as each row is created. That row starts out editing, and the Effect in
+        // `Item` focuses its input once the NodeRef is filled, that is once the node is mounted.
+        //
+        // ✅ edit_on_mount.set_value(Some(id));
+        let edit_on_mount = StoredValue::new(None::<u128>);
+
+        let on_add = move |value: String| {
+            let item = Item {
+                id: new_id(),
+                value: normalize_value(&value),
+                created_at: now_ms(),
+                ..Default::default()
+            };
+            if validate_item(&item).is_err() || blocked(None, &item.value) || full(1) {
+                return;
+            }
+            let id = item.id;
+            let before = record();
+            store.items().update(|items| items.push(item.clone()));
+            log_mutation("add", &before, store);
+            send(Mutation::Add(item));
+            edit_on_mount.set_value(Some(id));
+            toasts.push_toast("Item added", ToastKind::Success);
+        };
+
+        // Pasted lines are added as one `batch`, so however many there are the list notifies
+        // once, and they're saved and undone together as one change rather than each being
+        // queued on its own like a single add. Should `unique_values` block duplicates, lines
+        // repeating an item's value, or an earlier line's, are skipped rather than refusing the
+        // whole paste.
+        let on_add_lines = move |lines: Vec<String>| {
+            let created_at = now_ms();
+            let added = lines
+                .into_iter()
+                .map(|value| Item {
+                    id: new_id(),
+                    value: normalize_value(&value),
+                    created_at,
+                    ..Default::default()
+                })
+                .filter(|item| validate_item(item).is_ok())
+                .collect::<Vec<_>>();
+            let added = if unique_values.blocks() {
+                let pasted = added.len();
+                let mut seen = HashSet::new();
+                let added = added
+                    .into_iter()
+                    .filter(|item| {
+                        check_duplicate(None, item.value.clone()).is_none()
+                            && seen.insert(value_key(&item.value))
+                    })
+                    .collect::<Vec<_>>();
+                match pasted - added.len() {
+                    0 => {}
+                    1 => {
+                        toasts.push_toast("1 duplicate line was skipped", ToastKind::Info);
+                    }
+                    skipped => {
+                        toasts.push_toast(
+                            format!("{skipped} duplicate lines were skipped"),
+                            ToastKind::Info,
+                        );
+                    }
+                }
+                added
+            } else {
+                added
+            };
+            let added = fit(added, room());
+            if added.is_empty() {
+                return;
+            }
+            let count = added.len();
+            let before = record();
+            batch(store, |items| items.extend(added));
+            save("paste lines", before);
+            toasts.push_toast(
+                match count {
+                    1 => "1 item added".to_string(),
+                    count => format!("{count} items added"),
+                },
+                ToastKind::Success,
+            );
+        };
+
+        // Pressing "n" adds an item, which `on_add` opens for editing, so a list can be built
+        // without reaching for the mouse. Whilst typing into a field "n" is just a letter, so it's
+        // ignored there. Like the Ctrl+Z listener, this one is removed when the closure's owner is
+        // cleaned up.
+        let handle = window_event_listener(ev::keydown, move |ev| {
+            if ev.key() != "n" || ev.ctrl_key() || ev.meta_key() || ev.alt_key() || typing() {
+                return;
+            }
+            ev.prevent_default();
+            on_add("New item".to_string());
+        });
+        on_cleanup(move || handle.remove());
+
+        // A quantity is written through its own Field, rather than by updating the whole list,
+        // so only subscribers of that item's `quantity` are notified. The change is clamped, so
+        // decrementing below zero (or overflowing) leaves the quantity at its limit instead.
+        let on_quantity = move |id: u128, delta: i64| {
+            let before = record();
+            AtKeyed::new(store.items(), id)
+                .quantity()
+                .update(|quantity| {
+                    *quantity = (i64::from(*quantity) + delta).clamp(0, i64::from(u32::MAX)) as u32;
+                });
+            save("quantity", before);
+        };
+
+        // The total reads each item's `quantity` Field in turn, the IDs coming from a memo of
+        // their own, so a quantity written through its Field reruns it without touching
+        // anything else. Writes to the list as a whole, such as adding or editing an item, rerun
+        // it too. Either way, being a memo, the toolbar only re-renders when the total actually
+        // changes.
+        let ids = Memo::new(move |_| {
+            store
+                .items()
+                .with(|items| items.iter().map(|item| item.id).collect::<Vec<_>>())
+        });
+        // The count is derived from the IDs memo rather than from the list itself. Any write
+        // to the list reruns `ids`, editing a value included, but `ids` only notifies when the
+        // IDs have actually changed, so the count is only recomputed when an item has been
+        // added, deleted or moved, not edited. Reading `store.items()` here instead would be
+        // just as correct, only recomputing on every write.
+        let item_count = Memo::new(move |_| ids.with(Vec::len));
+        // The forms read the count too, so they disable themselves the moment the list fills.
+        let limit = Signal::derive(move || {
+            let count = item_count.get();
+            max_items
+                .filter(|max| count >= *max)
+                .map(|max| format!("Limit reached, {count}/{max}"))
+        });
+        let room_left =
+            Signal::derive(move || max_items.map(|max| max.saturating_sub(item_count.get())));
+        let total_quantity = Memo::new(move |_| {
+            ids.with(|ids| {
+                ids.iter()
+                    .map(|id| u64::from(AtKeyed::new(store.items(), *id).quantity().get()))
+                    .sum::<u64>()
+            })
+        });
+
+        // Tags live in a Vec nested within each item, and are likewise written through that
+        // item's own `tags` Field rather than the whole list. A tag is trimmed first, and blank
+        // or already present tags are ignored, so the chips' For never sees a duplicate key.
+        let on_add_tag = move |id: u128, tag: String| {
+            let tag = tag.trim().to_string();
+            let tags = AtKeyed::new(store.items(), id).tags();
+            if tag.is_empty() || tags.with_untracked(|tags| tags.contains(&tag)) {
+                return;
+            }
+            let before = record();
+            tags.update(|tags| tags.push(tag));
+            save("add tag", before);
+        };
+        let on_remove_tag = move |id: u128, tag: String| {
+            let before = record();
+            AtKeyed::new(store.items(), id)
+                .tags()
+                .update(|tags| tags.retain(|t| *t != tag));
+            save("remove tag", before);
+        };
+
+        // Sub-items are the deepest mutation in the demo, and the easiest to get wrong. The
+        // tempting `store.items().update(...)`, finding the parent and then pushing to its
+        // children, works but writes the whole list, notifying every row and rebuilding the
+        // keys of every keyed Field. Instead the write goes through the parent's own keyed
+        // `children` Field, so only that item's sub-items are notified and only their keys are
+        // refreshed, that Field's write guard doing so as it's dropped.
+        //
+        // The same rules as deleting an item apply one level down. The parent's and child's IDs
+        // arrive as raw copies, and a new child needs an ID of its own as the inner For is
+        // keyed on it.
+        let on_add_child = move |id: u128, value: String| {
+            let child = SubItem {
+                id: new_id(),
+                value: value.trim().to_string(),
+            };
+            if child.value.is_empty() {
+                return;
+            }
+            let before = record();
+            AtKeyed::new(store.items(), id)
+                .children()
+                .update(|children| children.push(child));
+            save("add sub-item", before);
+        };
+        let on_remove_child = move |id: u128, child_id: u128| {
+            let before = record();
+            AtKeyed::new(store.items(), id)
+                .children()
+                .update(|children| children.retain(|child| child.id != child_id));
+            save("remove sub-item", before);
+        };
+
+        // A status is a plain enum, written through the item's `status` Field like any other.
+        // The counts follow the same per-Field pattern as the quantity total.
+        let on_status = move |id: u128, status: Status| {
+            let field = AtKeyed::new(store.items(), id).status();
+            if field.get_untracked() == status {
+                return;
+            }
+            let before = record();
+            field.set(status);
+            save("status", before);
+        };
+        // Colour labels are written through their own Field too. Picking "No colour" clears it
+        // back to `None`.
+        let on_color = move |id: u128, color: Option<ColorLabel>| {
+            let field = AtKeyed::new(store.items(), id).color();
+            if field.get_untracked() == color {
+                return;
+            }
+            let before = record();
+            field.set(color);
+            save("colour", before);
+        };
+        // A due date is written through its own Field too, clearing the input setting it back
+        // to `None`.
+        let on_due = move |id: u128, due: Option<i64>| {
+            let field = AtKeyed::new(store.items(), id).due();
+            if field.get_untracked() == due {
+                return;
+            }
+            let before = record();
+            field.set(due);
+            save("due date", before);
+        };
+        let status_counts = Memo::new(move |_| {
+            let mut counts = [0; Status::ALL.len()];
+            ids.with(|ids| {
+                for id in ids {
+                    let status = AtKeyed::new(store.items(), *id).status().get();
+                    counts[status as usize] += 1;
+                }
+            });
+            counts
+        });
+
+        // Setting, editing and clearing a description all write the whole `Option` through the
+        // item's `description` Field. An unchanged description is left be, so merely focusing
+        // and leaving the text area doesn't record an undo step.
+        let on_description = move |id: u128, description: Option<String>| {
+            let field = AtKeyed::new(store.items(), id).description();
+            if field.with_untracked(|current| *current == description) {
+                return;
+            }
+            let before = record();
+            field.set(description);
+            save("description", before);
+        };
+
+        // Like quantities, completion is written through the item's own `done` Field. Only that
+        // row's checkbox and strikethrough, plus the counts below, react to it.
+        let on_toggle = move |id: u128| {
+            let before = record();
+            AtKeyed::new(store.items(), id)
+                .done()
+                .update(|done| *done = !*done);
+            save("toggle done", before);
+        };
+        // Favorites too are written through the item's own Field. Sorted favorites first, the
+        // sort below re-runs and the keyed For moves the row, leaving every other row be.
+        let on_favorite = move |id: u128| {
+            let before = record();
+            AtKeyed::new(store.items(), id)
+                .favorite()
+                .update(|favorite| *favorite = !*favorite);
+            save("favorite", before);
+        };
+        let done_count = Memo::new(move |_| {
+            ids.with(|ids| {
+                ids.iter()
+                    .filter(|id| AtKeyed::new(store.items(), **id).done().get())
+                    .count()
+            })
+        });
+        let progress = move || {
+            let total = ids.with(Vec::len);
+            if total == 0 {
+                0.0
+            } else {
+                done_count.get() as f64 / total as f64 * 100.0
+            }
+        };
+
+        // Importing reads the chosen file asynchronously, so the result is applied from a
+        // spawned task once it arrives. Either mode lands in the Store in a single update, and
+        // is undoable like any other mutation. A malformed file is reported inline and leaves
+        // the Store untouched.
+        let import_mode = RwSignal::new(ImportMode::Replace);
+        let import_error = RwSignal::new(None::<String>);
+        let on_import = move |data: Data| {
+            let mode = import_mode.get_untracked();
+            let imported = match mode {
+                ImportMode::Replace => fit(data.items, max_items),
+                ImportMode::Merge => fit(data.items, room()),
+            };
+            let imported = imported
+                .into_iter()
+                .map(|item| Item {
+                    value: normalize_value(&item.value),
+                    ..item
+                })
+                .collect::<Vec<_>>();
+            let before = record();
+            match mode {
+                ImportMode::Replace => restore(
+                    store,
+                    Data {
+                        items: imported,
+                        trash: before.trash.clone(),
+                    },
+                ),
+                ImportMode::Merge => store.items().update(|items| {
+                    items.extend(imported.into_iter().map(|item| Item {
+                        id: new_id(),
+                        ..item
+                    }));
+                }),
+            }
+            save("import", before);
+        };
+        // A list can also be shared as a link, carrying the list itself in the URL's fragment,
+        // so no server is involved. Opening one replaces the list much as importing it would,
+        // and is undoable just the same. The trash stays local to each browser, so it's neither
+        // shared nor replaced. Lists too long to fit in a link are offered as a file instead.
+        let on_share = move || {
+            let data = Data {
+                items: store.items().get_untracked(),
+                trash: Vec::new(),
+            };
+            let Some(link) = share::share_link(&data) else {
+                toasts.push_toast_with_action(
+                    "The list is too long to share as a link",
+                    ToastKind::Error,
+                    "Export a file",
+                    move || storage::export(&data),
+                );
+                return;
+            };
+            leptos::task::spawn_local(async move {
+                match clipboard::write_text(&link).await {
+                    Ok(()) => toasts.push_toast("Share link copied", ToastKind::Success),
+                    Err(error) => toasts.push_toast(
+                        format!("Couldn't copy the share link. {error}"),
+                        ToastKind::Error,
+                    ),
+                };
+            });
+        };
+        Effect::new(move |_| match share::take_from_location() {
+            Some(Ok(shared)) => {
+                let items = fit(shared.items, max_items);
+                let before = record();
+                let trash = before.trash.clone();
+                restore(store, Data { items, trash });
+                save("open share link", before);
+                toasts.push_toast("Opened a shared list", ToastKind::Info);
+            }
+            Some(Err(error)) => {
+                toasts.push_toast(
+                    format!("Couldn't open the shared list. {error}"),
+                    ToastKind::Error,
+                );
+            }
+            None => {}
+        });
+
+        let on_import_file = move |file: web_sys::File| {
+            leptos::task::spawn_local(async move {
+                let data = storage::read_file(file)
+                    .await
+                    .and_then(|json| storage::parse_import(&json));
+                match data {
+                    Ok(data) => {
+                        import_error.set(None);
+                        on_import(data);
+                    }
+                    Err(error) => import_error.set(Some(error)),
+                }
+            });
+        };
+
+        // The server and the browser disagree about what time it is, and would render different
+        // relative times, so rows show none until the client has hydrated. Only then, in an
+        // Effect, is `now` first set, and refreshed every thirty seconds thereafter.
+        let now = RwSignal::new(None::<i64>);
+        Effect::new(move |_| {
+            now.set(Some(now_ms()));
+            let handle =
+                set_interval_with_handle(move || now.set(Some(now_ms())), Duration::from_secs(30))
+                    .ok();
+            on_cleanup(move || {
+                if let Some(handle) = handle {
+                    handle.clear();
+                }
+            });
+        });
+
+        // The empty state is keyed off the list's length alone, so it appears after the last
+        // delete or a clear, and disappears as soon as anything is added (or undone back).
+        // Filtering everything out doesn't count, the items are still there.
+        let is_empty = Memo::new(move |_| store.items().with(Vec::is_empty));
+
+        // Screen readers are told when items come and go, through a live region rendered below.
+        // Rather than every handler announcing its own change, the Effect compares each list of
+        // items with the last, so whatever changed it, adding, deleting, undoing, importing or a
+        // refresh, is heard alike. Values are read too, so a deleted item can be named as it was
+        // when deleted, but edits alone leave the IDs as they were and announce nothing. The
+        // first run only takes note of the list the page was rendered with.
+        let announcement = RwSignal::new(String::new());
+        Effect::new(move |previous: Option<Vec<(u128, String)>>| {
+            let current = store.items().with(|items| {
+                items
+                    .iter()
+                    .map(|item| (item.id, item.value.clone()))
+                    .collect::<Vec<_>>()
+            });
+            if let Some(previous) = previous {
+                let describe = |others: &[(u128, String)], of: &[(u128, String)], verb: &str| {
+                    let changed = of
+                        .iter()
+                        .filter(|(id, _)| !others.iter().any(|(other, _)| other == id))
+                        .collect::<Vec<_>>();
+                    match changed.as_slice() {
+                        [] => None,
+                        [(_, value)] => Some(format!("{verb} item: {value}")),
+                        changed => Some(format!("{} items {}", changed.len(), verb.to_lowercase())),
+                    }
+                };
+                let message = [
+                    describe(&previous, &current, "Added"),
+                    describe(&current, &previous, "Deleted"),
+                ]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(". ");
+                if !message.is_empty() {
+                    announcement.set(message);
+                }
+            }
+            current
+        });
+
+        // A duplicate **must** be given a fresh ID. Reusing the source's ID would give the keyed
+        // For two rows with the same key, and the Store two items its keyed Fields can't tell
+        // apart.
+        let on_duplicate = move |id: u128| {
+            // The item may have gone since whatever asked, such as the context menu, was
+            // opened, by a reset, undo or import, in which case there's nothing to copy.
+            let Some(index) = store
+                .items()
+                .with_untracked(|items| items.iter().position(|item| item.id == id))
+            else {
+                return;
+            };
+            if full(1) {
+                return;
+            }
+            let before = record();
+            store.items().update(|items| {
+                let mut copy = items[index].clone();
+                copy.id = new_id();
+                copy.value.push_str(" (copy)");
+                items.insert(index + 1, copy);
+            });
+            save("duplicate", before);
+        };
+
+        // Splitting likewise gives every part but the first a fresh ID, see `split_by_id`.
+        let on_split = move |id: u128| {
+            let parts = store.items().with_untracked(|items| {
+                items
+                    .iter()
+                    .find(|item| item.id == id)
+                    .map_or(0, |item| split_value(&item.value).len())
+            });
+            if full(parts.saturating_sub(1)) {
+                return;
+            }
+            let before = record();
+            if split_by_id(store, id) {
+                save("split", before);
+            }
+        };
+
+        // Editing follows the same discipline. The ID and new value arrive as raw values, so
+        // nothing derived from the Vec is read once the write below begins. The keyed For keeps
+        // the row alive as only its `value` field changes.
+        //
+        // Like deleting, an edit is queued in `pending`, only sending the one item to
+        // `update_item`, which answers with its own canonical version, see `settle`.
+        let on_edit = move |id: u128, value: String| {
+            // The row has already checked the value, but checking again here, against the
+            // same `rules` as the detail page, means no caller can write one they'd refuse.
+            let checked = store
+                .items()
+                .with_untracked(|items| rules.check_edit(items, id, &value));
+            let (value, note) = match checked {
+                Ok((checked, note)) => (checked.value, note),
+                Err(message) => {
+                    toasts.push_toast(message, ToastKind::Error);
+                    return;
+                }
+            };
+            // An edit leaving the value as it was, once normalized, changes nothing, so there's
+            // nothing to record or send.
+            let unchanged = store.items().with_untracked(|items| {
+                items
+                    .iter()
+                    .any(|item| item.id == id && item.value == value)
+            });
+            if unchanged {
+                return;
+            }
+            if let Some(note) = note {
+                toasts.push_toast(note, ToastKind::Info);
+            }
+            let before = record();
+            update_value_by_id(store, id, value);
+            log_mutation("edit", &before, store);
+
+            if let Some(item) = store
+                .items()
+                .with_untracked(|items| items.iter().find(|item| item.id == id).cloned())
+            {
+                send(Mutation::Update(item));
+            }
+        };
+
+        // Reordering is a write to the Vec itself, so again the ID is read first and the item's
+        // position is found before `move_item` writes.
+        //
+        // Because the For below is keyed on each item's ID, swapping two elements doesn't
+        // recreate either row. The For sees the same two keys in new positions and simply moves
+        // the existing DOM nodes, so any state local to a row (such as an open edit input)
+        // survives the move.
+        let on_move = move |id: u128, direction: Direction| {
+            let Some(index) = store
+                .items()
+                .with_untracked(|items| items.iter().position(|item| item.id == id))
+            else {
+                return;
+            };
+            // Moving past either end is clamped by `move_item` into a no-op.
+            let target = match direction {
+                Direction::Up => index.saturating_sub(1),
+                Direction::Down => index + 1,
+            };
+            let before = record();
+            move_item(store, index, target);
+            save("move", before);
+        };
+
+        // Which row is being dragged, and which row it is hovering, is ephemeral view state used
+        // only to draw the insertion indicator. It never touches the Store.
+        let dragging = RwSignal::new(None::<u128>);
+        let drop_target = RwSignal::new(None::<u128>);
+
+        let on_drag = move |id: Option<u128>| {
+            dragging.set(id);
+            drop_target.set(None);
+        };
+
+        let on_drag_over = move |id: u128| {
+            if drop_target.get_untracked() != Some(id) {
+                drop_target.set(Some(id));
+            }
+        };
+
+        // Both IDs arrive as raw values read from the event and the target row **before** the
+        // write, so the dropped item takes the target's position (the item in that position
+        // shuffling along by one) without touching any Field derived from the Vec mid-update.
+        let on_drop = move |source: u128, target: u128| {
+            dragging.set(None);
+            drop_target.set(None);
+            if source == target {
+                return;
+            }
+            let positions = store.items().with_untracked(|items| {
+                let from = items.iter().position(|item| item.id == source)?;
+                let to = items.iter().position(|item| item.id == target)?;
+                Some((from, to))
+            });
+            let Some((from, to)) = positions else {
+                return;
+            };
+            let before = record();
+            move_item(store, from, to);
+            save("drop", before);
+        };
+
+        // Filtering only changes which items are rendered, never the Store itself. The memo
+        // recomputes the set of matching IDs whenever the query or the items change, but only
+        // notifies the For when that set actually differs.
+        //
+        // Because the For stays keyed on each item's ID, rows matching both before and after a
+        // change to the query are kept exactly as they are, and clearing the query recreates
+        // only the rows that had been hidden.
+        //
+        // The completion, tag and colour filters work the same way, and an item is only visible
+        // when it matches all four. Selecting several tags only shows items carrying every one of
+        // them. The counts shown beside each completion filter are of the items matching the
+        // query, tags and colour, so each is exactly how many rows choosing that filter would
+        // render.
+        //
+        // The query itself follows the search box keystroke by keystroke, but the filter reads
+        // `search`, which only catches up once typing has paused for 200ms. Each time the filter
+        // recomputes it lowercases and scans every item's value, and the completion counts scan
+        // them all again, three times over. Typing "banana" into a list of 10,000 items used to
+        // mean six rounds of that, one per letter, each also re-rendering whichever rows came
+        // and went along the way. Now, typed at any normal pace, it's one, the rows only
+        // changing once. The box itself stays bound to `query`, so typing is no less responsive,
+        // it's only the list that waits.
+        let query = RwSignal::new(String::new());
+        let search: Signal<String> = debounced_signal(query.into(), Duration::from_millis(200));
+        let completion = RwSignal::new(Completion::All);
+        let tag_filter = RwSignal::new(HashSet::<String>::new());
+        let color_filter = RwSignal::new(None::<ColorLabel>);
+        let matches =
+            move |item: &Item, query: &str, tags: &HashSet<String>, color: Option<ColorLabel>| {
+                item.value.to_lowercase().contains(query)
+                    && tags.iter().all(|tag| item.tags.contains(tag))
+                    && color.is_none_or(|color| item.color == Some(color))
+            };
+        let visible = Memo::new(move |_| {
+            let query = search.get().to_lowercase();
+            let completion = completion.get();
+            let color = color_filter.get();
+            tag_filter.with(|tags| {
+                store.items().with(|items| {
+                    items
+                        .iter()
+                        .filter(|item| {
+                            matches(item, &query, tags, color) && completion.matches(item)
+                        })
+                        .map(|item| item.id)
+                        .collect::<HashSet<_>>()
+                })
+            })
+        });
+        let completion_count = move |completion: Completion| {
+            let query = search.get().to_lowercase();
+            let color = color_filter.get();
+            tag_filter.with(|tags| {
+                store.items().with(|items| {
+                    items
+                        .iter()
+                        .filter(|item| {
+                            matches(item, &query, tags, color) && completion.matches(item)
+                        })
+                        .count()
+                })
+            })
+        };
+
+        // Every tag in use, sorted, offered as filters. Derived from the items, so it follows
+        // tags as they're added and removed. A filtered tag that no item carries any longer is
+        // dropped from the filter too, lest it hide everything with no chip left to unselect.
+        let available_tags = Memo::new(move |_| {
+            store.items().with(|items| {
+                items
+                    .iter()
+                    .flat_map(|item| item.tags.iter().cloned())
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect::<Vec<_>>()
+            })
+        });
+        Effect::new(move |_| {
+            available_tags.with(|available| {
+                let stale =
+                    tag_filter.with_untracked(|tags| tags.iter().any(|t| !available.contains(t)));
+                if stale {
+                    tag_filter.update(|tags| tags.retain(|t| available.contains(t)));
+                }
+            });
+        });
+        let on_filter_tag = move |tag: String| {
+            tag_filter.update(|tags| {
+                if !tags.remove(&tag) {
+                    tags.insert(tag);
+                }
+            });
+        };
+
+        // Which items are selected is view state, kept alongside the Store as a set of raw IDs.
+        let selected = RwSignal::new(HashSet::<u128>::new());
+        let on_select = move |id: u128| {
+            selected.update(|selected| {
+                if !selected.remove(&id) {
+                    selected.insert(id);
+                }
+            });
+        };
+
+        // Deleting the selection is a single batched pass over the Vec, retaining only
+        // unselected items, rather than a `remove_by_id` per item. Selecting a hundred items and
+        // deleting them notifies the list once, not a hundred times, see `batch`.
+        let on_delete_selected = move || {
+            let ids = selected.get_untracked();
+            if ids.is_empty() {
+                return;
+            }
+            let before = record();
+            let deleted = batch(store, |items| {
+                let (kept, removed): (Vec<_>, Vec<_>) = std::mem::take(items)
+                    .into_iter()
+                    .partition(|item| !ids.contains(&item.id));
+                *items = kept;
+                removed
+            });
+            store.trash().update(|trash| {
+                trash.splice(0..0, deleted);
+            });
+            save("delete selected", before);
+            selected.write().clear();
+        };
+
+        // Merging takes exactly two selected items, the later of the two in the list being
+        // merged into the earlier, see `merge_by_id`. It's recorded first, as ever, so it can be
+        // undone, restoring both. The absorbed item's ID is dropped from the selection along
+        // with it.
+        let can_merge = Memo::new(move |_| selected.with(HashSet::len) == 2);
+        let on_merge_selected = move || {
+            let mut ids = selected.get_untracked().into_iter().collect::<Vec<_>>();
+            if ids.len() != 2 {
+                return;
+            }
+            store.items().with_untracked(|items| {
+                ids.sort_by_key(|id| items.iter().position(|item| item.id == *id));
+            });
+            let before = record();
+            if merge_by_id(store, ids[0], ids[1]) {
+                save("merge", before);
+                selected.set(HashSet::from([ids[0]]));
+            }
+        };
+
+        // Like every other mutation clearing is recorded first, so it can be undone. An empty Vec
+        // simply leaves the keyed For with no rows, and the selection is pruned below.
+        let clear = move || {
+            let before = record();
+            let deleted = batch(store, std::mem::take);
+            store.trash().update(|trash| {
+                trash.splice(0..0, deleted);
+            });
+            save("clear all", before);
+        };
+
+        // Rows fade and collapse in and out, rather than popping, see `.row` in
+        // `style/tailwind.css`.
+        //
+        // Entering is left to CSS alone. A new row's wrapper is rendered with `row-enter`, whose
+        // `@starting-style` is where the transition starts from. Only rows created after
+        // hydration get it, so the list doesn't animate in on page load.
+        //
+        // Leaving is the harder half. The keyed For removes a row's nodes the moment its ID
+        // leaves the list, so there's no chance to animate them. Instead the ID is first added to
+        // `leaving`, which collapses the row, and the item is only deleted from the Store once
+        // the transition has had `LEAVE` to finish. Throughout, the item is still in the Store
+        // under its own ID, so its row keeps its key. New items are always given a fresh ID, and
+        // `on_undo_delete` won't reinsert an ID still present, so no other row can claim that key
+        // whilst it's leaving. Deleting an item that's already leaving does nothing.
+        const LEAVE: Duration = Duration::from_millis(200);
+        let hydrated = StoredValue::new(false);
+        Effect::new(move |_| hydrated.set_value(true));
+        let leaving = RwSignal::new(HashSet::<u128>::new());
+        let leave = move |id: u128| {
+            if leaving.with_untracked(|leaving| leaving.contains(&id)) {
+                return;
+            }
+            leaving.update(|leaving| {
+                leaving.insert(id);
+            });
+            set_timeout(
+                move || {
+                    // Should the list have gone in the meantime, so has the history to record
+                    // the delete in, so the item is left be.
+                    if leaving.try_update(|leaving| leaving.remove(&id)).is_some() {
+                        delete(id);
+                    }
+                },
+                LEAVE,
+            );
+        };
+
+        // Deleting a row and clearing the list both ask first, through a `ConfirmDialog`. Which
+        // action is waiting on an answer is the only state needed, the dialog being rendered
+        // whilst there is one. The row's Delete button still passes its raw ID up through
+        // `on_delete` as before, it's just that nothing is deleted until the dialog confirms.
+        let confirming = RwSignal::new(None::<Confirm>);
+        let on_delete = move |id: u128| {
+            if !pending_ids.with_untracked(|ids| ids.contains(&id)) {
+                confirming.set(Some(Confirm::Delete(id)));
+            }
+        };
+        let on_clear = move || confirming.set(Some(Confirm::Clear));
+        let on_restore = move |id: u128| restore_item(id, None);
+        let on_purge = move |id: u128| confirming.set(Some(Confirm::Purge(id)));
+        let on_empty_trash = move || confirming.set(Some(Confirm::EmptyTrash));
+        // Only asks when there's something to lose.
+        let on_reset = move || {
+            if dirty.get_untracked() {
+                confirming.set(Some(Confirm::Reset));
+            } else {
+                reset_action.dispatch(());
+            }
+        };
+        let on_cancel = move || confirming.set(None);
+
+        // Right-clicking a row opens its context menu at the cursor, offering what its buttons
+        // already do. There's only the one menu, rendered by `Items` rather than each row, so
+        // opening another row's closes the first. It carries the item's raw ID, and each entry
+        // goes through the same callback as the equivalent button.
+        //
+        // Editing is the exception, as whether a row is being edited is state only the row has.
+        // Instead `edit_request` names the row to start editing, which it watches for. The
+        // request only stands for a frame, lest a row rendered afresh later, say once a filter
+        // is cleared, take it as its own and start editing out of nowhere.
+        let context_menu = RwSignal::new(None::<(u128, i32, i32)>);
+        let edit_request = RwSignal::new(None::<u128>);
+        let on_context_menu = move |id: u128, x: i32, y: i32| context_menu.set(Some((id, x, y)));
+        let on_close_menu = move || context_menu.set(None);
+        let on_menu_edit = move |id: u128| {
+            edit_request.set(Some(id));
+            request_animation_frame(move || {
+                edit_request.try_set(None);
+            });
+        };
+        // Copying, from the menu or the row's own Copy button, only reads the Store, so there's
+        // nothing to record or save. The value is looked up when asked, rather than passed up by
+        // the row, so it's always the Store's.
+        let on_copy = move |id: u128| {
+            let Some(value) = store
+                .items()
+                .with_untracked(|items| items.iter().find(|i| i.id == id).map(|i| i.value.clone()))
+            else {
+                return;
+            };
+            leptos::task::spawn_local(async move {
+                match clipboard::write_text(&value).await {
+                    Ok(()) => toasts.push_toast(format!("Copied \"{value}\""), ToastKind::Success),
+                    Err(error) => toasts.push_toast(error, ToastKind::Error),
+                };
+            });
+        };
+
+        // Whether an item's sub-items are shown is view state too. It's tempting to add an
+        // `expanded: bool` to `Item` instead, but then merely expanding a row would be saved to
+        // the server and localStorage, recorded for undo, and count as a change to the item
+        // everywhere it's rendered. Kept here as a set of raw IDs it's simply forgotten on
+        // reload, as view state should be. Unlike the selection below, deleted items' IDs are
+        // left be, so undoing a delete brings the row back just as it was.
+        let expanded = RwSignal::new(HashSet::<u128>::new());
+        let on_expand = move |id: u128| {
+            expanded.update(|expanded| {
+                if !expanded.remove(&id) {
+                    expanded.insert(id);
+                }
+            });
+        };
+        let on_expand_all = move || {
+            expanded.set(
+                store
+                    .items()
+                    .with_untracked(|items| items.iter().map(|item| item.id).collect()),
+            );
+        };
+
+        // Items can disappear by other means too, such as "Delete 0" or undo, so prune any
+        // selected IDs no longer in the Store rather than leaving them dangling.
+        Effect::new(move |_| {
+            let ids = store
+                .items()
+                .with(|items| items.iter().map(|item| item.id).collect::<HashSet<_>>());
+            if selected.with_untracked(|selected| selected.iter().any(|id| !ids.contains(id))) {
+                selected.update(|selected| selected.retain(|id| ids.contains(id)));
+            }
+        });
+
+        // Sorting likewise leaves the Vec untouched. The memo clones the IDs out of the Store and
+        // sorts those, recomputing only when the items or the sort settings change. Sorting by
+        // value reads every value, so using "Mutate n-1" (or editing a value) while sorted by
+        // value moves that row to its new position, the keyed For moving rather than recreating
+        // it. `sort_by` is stable, so items with equal values keep their order from the Vec.
+        //
+        // With no sort key the Vec's own order is used, which is the order that "Move up",
+        // "Move down" and dragging rearrange. Sorting favorites first is a stable partition, as
+        // all favorites compare equal to one another, as do all the rest, so each group keeps
+        // the Vec's order.
+        let sort_key = RwSignal::new(None::<SortKey>);
+        let ascending = RwSignal::new(true);
+        let sorted = Memo::new(move |_| {
+            let sort_key = sort_key.get();
+            let ascending = ascending.get();
+            store.items().with(|items| {
+                let mut items = items.iter().collect::<Vec<_>>();
+                if let Some(sort_key) = sort_key {
+                    items.sort_by(|a, b| {
+                        let ordering = match sort_key {
+                            SortKey::Id => a.id.cmp(&b.id),
+                            SortKey::Value => a.value.cmp(&b.value),
+                            SortKey::Favorite => b.favorite.cmp(&a.favorite),
+                            SortKey::Due => match (a.due, b.due) {
+                                (Some(a), Some(b)) => a.cmp(&b),
+                                // Undated items go last whichever way it's sorted, so only the
+                                // dates are compared in reverse.
+                                (a, b) => return a.is_none().cmp(&b.is_none()),
+                            },
+                            SortKey::Quantity => a.quantity.cmp(&b.quantity),
+                            SortKey::Done => a.done.cmp(&b.done),
+                            SortKey::Status => (a.status as u8).cmp(&(b.status as u8)),
+                            SortKey::Created => a.created_at.cmp(&b.created_at),
+                        };
+                        if ascending {
+                            ordering
+                        } else {
+                            ordering.reverse()
+                        }
+                    });
+                }
+                items.into_iter().map(|item| item.id).collect::<Vec<_>>()
+            })
+        });
+
+        // The IDs actually rendered, in order, after filtering and sorting.
+        let rendered = Memo::new(move |_| {
+            let visible = visible.get();
+            sorted
+                .get()
+                .into_iter()
+                .filter(|id| visible.contains(id))
+                .collect::<Vec<_>>()
+        });
+
+        // The search query, sort and filters are mirrored in the URL's query string, such as
+        // `/?q=milk&sort=value-asc&tag=shop`, so a filtered view can be shared and survives a
+        // reload. The URL is the source of truth whenever it changes, and the signals above
+        // whenever they do:
+        //
+        // - On creation the signals are set from the URL before anything is rendered. This
+        //   happens on the server too, so SSR renders the very list the URL describes, and
+        //   hydration matches.
+        // - Navigating back or forward changes the URL, and the first Effect below sets the
+        //   signals to match.
+        // - Changing a signal, by typing or choosing a filter, makes the second Effect navigate
+        //   to the matching URL. The query only makes it there once typing pauses, see
+        //   `debounce`, so there isn't a history entry per keystroke.
+        //
+        // Either Effect leads to the other running too, but each compares before writing and
+        // finds nothing to do. Only parameters that differ from the defaults are written.
+        let query_map = use_query_map();
+        let location = use_location();
+        let navigate = use_navigate();
+        let committed_query = RwSignal::new(String::new());
+        let apply_params = move |params: &ParamsMap| {
+            let q = params.get("q").unwrap_or_default();
+            let (key, direction) = parse_sort_param(params.get_str("sort").unwrap_or_default());
+            let filter = params
+                .get_str("status")
+                .and_then(|param| Completion::ALL.into_iter().find(|c| c.param() == param))
+                .unwrap_or(Completion::All);
+            let tags = params
+                .get_all("tag")
+                .unwrap_or_default()
+                .into_iter()
+                .collect::<HashSet<_>>();
+            let color = params
+                .get_str("color")
+                .and_then(|param| ColorLabel::ALL.into_iter().find(|c| c.param() == param));
+            if query.get_untracked() != q {
+                query.set(q.clone());
+            }
+            if committed_query.get_untracked() != q {
+                committed_query.set(q);
+            }
+            if sort_key.get_untracked() != key {
+                sort_key.set(key);
+            }
+            if ascending.get_untracked() != direction {
+                ascending.set(direction);
+            }
+            if completion.get_untracked() != filter {
+                completion.set(filter);
+            }
+            if tag_filter.with_untracked(|tag_filter| *tag_filter != tags) {
+                tag_filter.set(tags);
+            }
+            if color_filter.get_untracked() != color {
+                color_filter.set(color);
+            }
+        };
+        query_map.with_untracked(apply_params);
+        Effect::new(move |_| query_map.with(apply_params));
+        debounce(
+            query.into(),
+            Duration::from_millis(300),
+            move |q: String| {
+                committed_query.try_set(q);
+            },
+        );
+        Effect::new(move |_| {
+            let mut params = ParamsMap::new();
+            let q = committed_query.get();
+            if !q.is_empty() {
+                params.insert("q", q);
+            }
+            let sort = sort_param(sort_key.get(), ascending.get());
+            if sort != "manual" {
+                params.insert("sort", sort.to_string());
+            }
+            let filter = completion.get();
+            if filter != Completion::All {
+                params.insert("status", filter.param().to_string());
+            }
+            let mut tags = tag_filter.get().into_iter().collect::<Vec<_>>();
+            tags.sort();
+            for tag in tags {
+                params.insert("tag", tag);
+            }
+            if let Some(color) = color_filter.get() {
+                params.insert("color", color.param().to_string());
+            }
+            let search = params.to_query_string();
+            if query_map.with_untracked(ParamsMap::to_query_string) != search {
+                let path = location.pathname.get_untracked();
+                navigate(
+                    &format!("{path}{search}"),
+                    NavigateOptions {
+                        scroll: false,
+                        ..Default::default()
+                    },
+                );
+            }
+        });
+
+        // The master checkbox only ever (de)selects the rendered items, so whilst filtering it
+        // leaves any hidden items' selection as it was. An indeterminate checkbox can only be set
+        // through the DOM property, hence the NodeRef.
+        let all_selected = Memo::new(move |_| {
+            let rendered = rendered.get();
+            !rendered.is_empty() && selected.with(|s| rendered.iter().all(|id| s.contains(id)))
+        });
+        let some_selected = Memo::new(move |_| {
+            let rendered = rendered.get();
+            selected.with(|s| rendered.iter().any(|id| s.contains(id)))
+        });
+        let select_all_ref = NodeRef::<leptos::html::Input>::new();
+        Effect::new(move |_| {
+            let indeterminate = some_selected.get() && !all_selected.get();
+            if let Some(input) = select_all_ref.get() {
+                input.set_indeterminate(indeterminate);
+            }
+        });
+        let on_select_all = move || {
+            let rendered = rendered.get_untracked();
+            if all_selected.get_untracked() {
+                selected.update(|s| s.retain(|id| !rendered.contains(id)));
+            } else {
+                selected.update(|s| s.extend(rendered));
+            }
+        };
+
+        // Grouping by the first letter of each value buckets the rendered IDs, so it follows the
+        // same filters and sort as the flat list, and each group keeps their order. The memo reads
+        // every value, so editing a value to start with another letter moves the item into that
+        // letter's group. Rows are keyed within their group's own For, so moving group recreates
+        // the row, whilst rows that stay put are left be. Which groups are collapsed is view
+        // state, kept here rather than in the Store, like `expanded`.
+        //
+        // The items are indexed by ID once per run, rather than each rendered ID searching the
+        // list, which would be quadratic in its length. Whilst the list isn't grouped nothing
+        // is read at all, so writes to it don't rerun the memo.
+        let group_by_letter = RwSignal::new(false);
+        let collapsed = RwSignal::new(HashSet::<String>::new());
+        let groups = Memo::new(move |_| {
+            let mut groups = Vec::<(String, Vec<u128>)>::new();
+            if !group_by_letter.get() {
+                return groups;
+            }
+            store.items().with(|items| {
+                let by_id = items
+                    .iter()
+                    .map(|item| (item.id, item))
+                    .collect::<HashMap<_, _>>();
+                for id in rendered.get() {
+                    let Some(item) = by_id.get(&id) else {
+                        continue;
+                    };
+                    let letter = group_letter(&item.value);
+                    match groups.iter_mut().find(|(l, _)| *l == letter) {
+                        Some((_, ids)) => ids.push(id),
+                        None => groups.push((letter, vec![id])),
+                    }
+                }
+            });
+            groups.sort_by_key(|(letter, _)| (letter == "#", letter.clone()));
+            groups
+        });
+        let on_collapse = move |letter: String| {
+            collapsed.update(|collapsed| {
+                if !collapsed.remove(&letter) {
+                    collapsed.insert(letter);
+                }
+            });
+        };
+
+        // The table is another way of rendering the same rows, so which view is chosen is view
+        // state too. It's one flat list, ignoring `group_by_letter`, with the sort select's sort
+        // driven by its column headers instead.
+        let table_view = RwSignal::new(false);
+
+        // Coming back to the list from an item's page, say, scrolls back down to where it was.
+        let list_ref = NodeRef::<leptos::html::Div>::new();
+        restore_scroll(list_ref);
+
+        // The rows can be moved between with the Up and Down arrow keys, using a roving
+        // tabindex. Only one row at a time is in the tab order, so Tab moves past the whole list
+        // in one go, and the arrows then move focus from row to row. Within a focused row Enter
+        // edits its value and Delete asks to delete it, see `Item`.
+        //
+        // Which row is focused is view state, kept as the item's ID, never its index. Sorting,
+        // filtering, reordering or deleting other rows all shift indices about, but the keyed
+        // For keeps each row's element for as long as its ID is rendered, so focus stays with
+        // the item. `focused` is kept up to date by the rows themselves, whenever focus moves
+        // into one, however it got there. Should the focused item stop being rendered, the first
+        // rendered row takes its place in the tab order.
+        //
+        // The order moved through is the one on screen, so grouped by letter it runs through
+        // each group in turn, skipping those collapsed. The table view has its own rows, which
+        // are left to the browser's own tabbing.
+        let focused = RwSignal::new(None::<u128>);
+        let navigable = Memo::new(move |_| {
+            if group_by_letter.get() {
+                collapsed.with(|collapsed| {
+                    groups.with(|groups| {
+                        groups
+                            .iter()
+                            .filter(|(letter, _)| !collapsed.contains(letter))
+                            .flat_map(|(_, ids)| ids.iter().copied())
+                            .collect::<Vec<_>>()
+                    })
+                })
+            } else {
+                rendered.get()
+            }
+        });
+        let tabbable = Memo::new(move |_| {
+            navigable.with(|ids| {
+                focused
+                    .get()
+                    .filter(|id| ids.contains(id))
+                    .or_else(|| ids.first().copied())
+            })
+        });
+        let focus_row = move |id: u128| {
+            focused.set(Some(id));
+            if let Some(row) = document()
+                .get_element_by_id(&row_element_id(id))
+                .and_then(|row| row.dyn_into::<web_sys::HtmlElement>().ok())
+            {
+                let _ = row.focus();
+            }
+        };
+        let on_nav = move |id: u128, direction: Direction| {
+            let next = navigable.with_untracked(|ids| {
+                let index = ids.iter().position(|other| *other == id)?;
+                match direction {
+                    Direction::Up => index.checked_sub(1).map(|index| ids[index]),
+                    Direction::Down => ids.get(index + 1).copied(),
+                }
+            });
+            if let Some(next) = next {
+                focus_row(next);
+            }
+        };
+        // Deleting the focused row moves focus on to the row after it, or failing that the one
+        // before, once the dialog has gone and handed focus back to the row being deleted.
+        let focus_after_delete = move |id: u128| {
+            if focused.get_untracked() != Some(id) {
+                return;
+            }
+            let next = navigable.with_untracked(|ids| {
+                let index = ids.iter().position(|other| *other == id)?;
+                ids.get(index + 1)
+                    .or_else(|| index.checked_sub(1).and_then(|index| ids.get(index)))
+                    .copied()
+            });
+            if let Some(next) = next {
+                request_animation_frame(move || focus_row(next));
+            }
+        };
+
+        // Answers whichever `ConfirmDialog` is open, see `confirming` above. It's down here as a
+        // confirmed delete also moves focus on, see `focus_after_delete`.
+        let on_confirm = move || match confirming.get_untracked() {
+            Some(Confirm::Delete(id)) => {
+                confirming.set(None);
+                focus_after_delete(id);
+                leave(id);
+            }
+            Some(Confirm::Clear) => {
+                confirming.set(None);
+                clear();
+            }
+            Some(Confirm::Purge(id)) => {
+                confirming.set(None);
+                purge(id);
+            }
+            Some(Confirm::EmptyTrash) => {
+                confirming.set(None);
+                empty_trash();
+            }
+            Some(Confirm::Reset) => {
+                confirming.set(None);
+                reset_action.dispatch(());
+            }
+            None => {}
+        };
+
+        // What each row can ask of the list, provided once rather than handed to every row,
+        // see `ItemCallbacks`.
+        provide_context(ItemCallbacks {
+            on_select: Callback::new(on_select),
+            on_delete: Callback::new(on_delete),
+            on_duplicate: Callback::new(on_duplicate),
+            on_copy: Callback::new(on_copy),
+            on_split: Callback::new(on_split),
+            on_quantity: Callback::new(move |(id, delta)| on_quantity(id, delta)),
+            on_toggle: Callback::new(on_toggle),
+            on_favorite: Callback::new(on_favorite),
+            on_add_tag: Callback::new(move |(id, tag)| on_add_tag(id, tag)),
+            on_remove_tag: Callback::new(move |(id, tag)| on_remove_tag(id, tag)),
+            on_add_child: Callback::new(move |(id, value)| on_add_child(id, value)),
+            on_remove_child: Callback::new(move |(id, child)| on_remove_child(id, child)),
+            on_expand: Callback::new(on_expand),
+            on_description: Callback::new(move |(id, description)| on_description(id, description)),
+            on_status: Callback::new(move |(id, status)| on_status(id, status)),
+            on_color: Callback::new(move |(id, color)| on_color(id, color)),
+            on_due: Callback::new(move |(id, due)| on_due(id, due)),
+            on_edit: Callback::new(move |(id, value)| on_edit(id, value)),
+            check_duplicate: Callback::new(move |(id, value)| check_duplicate(Some(id), value)),
+            on_move: Callback::new(move |(id, direction)| on_move(id, direction)),
+            on_drag: Callback::new(on_drag),
+            on_drag_over: Callback::new(on_drag_over),
+            on_drop: Callback::new(move |(source, target)| on_drop(source, target)),
+            on_focus_row: Callback::new(move |id| focused.set(Some(id))),
+            on_nav: Callback::new(move |(id, direction)| on_nav(id, direction)),
+            on_context_menu: Callback::new(move |(id, x, y)| on_context_menu(id, x, y)),
+        });
+
+        // Renders a single row, whether in the flat list or within a letter's group above.
+        let row = move |id: u128| {
+            // The For now iterates IDs, so each row's Field is looked up by its key.
+            // A row's ID never changes, so it's also safe to use it to work out the
+            // row's position whenever the Vec changes.
+            let item = AtKeyed::new(store.items(), id);
+            let first = Signal::derive(move || {
+                store
+                    .items()
+                    .with(|items| items.first().map(|i| i.id) == Some(id))
+            });
+            let last = Signal::derive(move || {
+                store
+                    .items()
+                    .with(|items| items.last().map(|i| i.id) == Some(id))
+            });
+            // The dropped item will take this row's place, so the indicator is drawn
+            // on whichever side of this row the dragged item will end up.
+            let drop_indicator = Signal::derive(move || {
+                let source = dragging.get()?;
+                if drop_target.get() != Some(id) || source == id {
+                    return None;
+                }
+                store.items().with(|items| {
+                    let from = items.iter().position(|i| i.id == source)?;
+                    let to = items.iter().position(|i| i.id == id)?;
+                    Some(if from < to {
+                        Direction::Down
+                    } else {
+                        Direction::Up
+                    })
+                })
+            });
+            // Only the row being created for a just-added item starts out editing,
+            // and only the once, so rows recreated later by filtering don't.
+            let start_editing = edit_on_mount.get_value() == Some(id);
+            if start_editing {
+                edit_on_mount.set_value(None);
+            }
+            view! {
+                <div
+                    class="row"
+                    role="listitem"
+                    class=("row-enter", hydrated.get_value())
+                    class=("leaving", move || leaving.with(|l| l.contains(&id)))
+                >
+                    <Item
+                        item
+                        first
+                        last
+                        drop_indicator
+                        selected=Signal::derive(move || selected.with(|s| s.contains(&id)))
+                        highlight=search
+                        markdown=preferences.markdown()
+                        deleting=Signal::derive(move || deleting(id))
+                        busy=Signal::derive(move || pending_ids.with(|ids| ids.contains(&id)))
+                        expanded=Signal::derive(move || expanded.with(|e| e.contains(&id)))
+                        now
+                        duplicated=Signal::derive(move || duplicate_ids.with(|ids| ids.contains(&id)))
+                        tabbable=Signal::derive(move || tabbable.get() == Some(id))
+                        edit_requested=Signal::derive(move || edit_request.get() == Some(id))
+                        max_length
+                        over_length
+                        start_editing
+                    />
+                </div>
+            }
+        };
+
+        // The table view, built from the same rendered IDs as the cards. Its type is erased as
+        // the row's is, see `ItemTableRow`.
+        let table = move || {
+            view! {
+                <table class="w-full border-collapse">
+                    <thead class="text-sm text-neutral-500">
+                        <tr>
+                            <SortHeader label="ID" key=SortKey::Id sort_key ascending />
+                            <SortHeader label="Value" key=SortKey::Value sort_key ascending />
+                            <SortHeader label="Quantity" key=SortKey::Quantity sort_key ascending />
+                            <SortHeader label="Done" key=SortKey::Done sort_key ascending />
+                            <SortHeader label="Status" key=SortKey::Status sort_key ascending />
+                            <SortHeader label="Favorite" key=SortKey::Favorite sort_key ascending />
+                            <SortHeader label="Due" key=SortKey::Due sort_key ascending />
+                            <SortHeader label="Created" key=SortKey::Created sort_key ascending />
+                            <th></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        <For
+                            each=move || rendered.get()
+                            key=|id| *id
+                            children=move |id| view! {
+                                <ItemTableRow
+                                    item=AtKeyed::new(store.items(), id)
+                                    on_delete
+                                    on_toggle
+                                    on_favorite
+                                    on_status
+                                    on_edit
+                                    check_duplicate=move |id, value| check_duplicate(Some(id), value)
+                                    now
+                                />
+                            }
+                        />
+                    </tbody>
+                </table>
+            }
+            .into_any()
+        };
+
+        Ok::<_, ServerFnError>(view! {
+            <AddItem
+                on_add
+                limit
+                check_duplicate=Callback::new(move |value: String| check_duplicate(None, value))
+            />
+            <AddLines on_add_lines room=room_left />
+            <div class="flex gap-2 items-center mb-4 text-sm text-neutral-500">
+                <span class="whitespace-nowrap">
+                    { move || format!("{} of {} done", done_count.get(), ids.with(Vec::len)) }
+                </span>
+                <div class="flex-grow h-2 bg-neutral-200 rounded overflow-hidden">
+                    <div
+                        class="h-full bg-green-600 transition-[width]"
+                        style:width=move || format!("{}%", progress())
+                    ></div>
+                </div>
+            </div>
+            <div class="flex flex-wrap gap-2 mb-4">
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded"
+                    on:click=move |_| {
+                        let before = record();
+                        store.items().update(|items| {
+                            let len = items.len();
+                            if len >= 2 {
+                                let item = items.get_mut(len - 2).unwrap();
+                                item.value = "Mutated".to_string();
+                            }
+                        });
+                        save("mutate n-1", before);
+                    }
+                >
+                    Mutate n-1
+                </button>
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded"
+                    on:click=move |_| {
+                        let first = store.items().with_untracked(|items| items.first().map(|i| i.id));
+                        if let Some(id) = first {
+                            let before = record();
+                            trash_by_id(store, id);
+                            save("delete 0", before);
+                        }
+                    }
+                >
+                    Delete 0
+                </button>
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 disabled:opacity-50 px-4 py-2 rounded"
+                    disabled=move || selected.with(HashSet::is_empty)
+                    on:click=move |_| on_delete_selected()
+                >
+                    { move || format!("Delete selected ({})", selected.with(HashSet::len)) }
+                </button>
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 disabled:opacity-50 px-4 py-2 rounded"
+                    title="Merge the later of two selected items into the earlier"
+                    disabled=move || !can_merge.get()
+                    on:click=move |_| on_merge_selected()
+                >
+                    Merge selected
+                </button>
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded"
+                    on:click=move |_| on_clear()
+                >
+                    Clear all
+                </button>
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 disabled:opacity-50 px-4 py-2 rounded"
+                    disabled=move || !history.with(History::can_undo)
+                    on:click=move |_| undo()
+                >
+                    Undo
+                </button>
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 disabled:opacity-50 px-4 py-2 rounded"
+                    disabled=move || !history.with(History::can_redo)
+                    on:click=move |_| redo()
+                >
+                    Redo
+                </button>
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 disabled:opacity-50 px-4 py-2 rounded"
+                    disabled=move || refresh_action.pending().get()
+                    on:click=move |_| {
+                        refresh_action.dispatch(());
+                    }
+                >
+                    { move || if refresh_action.pending().get() { "Refreshing…" } else { "Refresh" } }
+                </button>
+                <label
+                    class="self-center flex gap-2 items-center text-sm"
+                    title=format!("Refresh every {poll_secs} seconds")
+                >
+                    <input type="checkbox" bind:checked=polling />
+                    Auto-refresh
+                </label>
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 disabled:opacity-50 px-4 py-2 rounded"
+                    disabled=move || reset_action.pending().get()
+                    on:click=move |_| on_reset()
+                >
+                    Reset to server
+                </button>
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded"
+                    on:click=move |_| storage::export(&snapshot(store))
+                >
+                    Export
+                </button>
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded"
+                    on:click=move |_| storage::export_csv(&store.items().get_untracked())
+                >
+                    Export CSV
+                </button>
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded"
+                    on:click=move |_| on_share()
+                >
+                    Share link
+                </button>
+                // Keeps the query as it is, so the filters are unchanged on coming back, see
+                // `Home`.
+                <a
+                    class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded"
+                    href=move || {
+                        let mut params = query_map.get();
+                        params.insert("print", "1".to_string());
+                        format!("/{}", params.to_query_string())
+                    }
+                >
+                    Print view
+                </a>
+                <label class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded cursor-pointer">
+                    Import
+                    <input
+                        type="file"
+                        accept=".json,application/json"
+                        class="hidden"
+                        on:change=move |ev| {
+                            let input = event_target::<web_sys::HtmlInputElement>(&ev);
+                            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                                on_import_file(file);
+                            }
+                            // Cleared so choosing the same file again still fires a change.
+                            input.set_value("");
+                        }
+                    />
+                </label>
+                <select
+                    class="border border-neutral-300 px-2 py-1 rounded"
+                    title="Import mode"
+                    on:change=move |ev| {
+                        import_mode.set(match event_target_value(&ev).as_str() {
+                            "merge" => ImportMode::Merge,
+                            _ => ImportMode::Replace,
+                        });
+                    }
+                >
+                    <option value="replace">Replace</option>
+                    <option value="merge">Merge</option>
+                </select>
+                <span class="self-center text-sm text-neutral-500">
+                    {move || match item_count.get() {
+                        1 => "1 item".to_string(),
+                        count => format!("{count} items"),
+                    }}
+                </span>
+                <span class="self-center text-sm text-neutral-500">
+                    { move || format!("Total {}", total_quantity.get()) }
+                </span>
+                <span class="self-center text-sm text-neutral-500">
+                    {move || {
+                        let counts = status_counts.get();
+                        Status::ALL
+                            .iter()
+                            .map(|status| format!("{} {}", status.label(), counts[*status as usize]))
+                            .collect::<Vec<_>>()
+                            .join(" · ")
+                    }}
+                </span>
+                <span class="self-center text-sm text-neutral-500">{save_status}</span>
+                <Show when=move || dirty.get()>
+                    <span
+                        class="self-center text-sm text-amber-600"
+                        title="There are changes the server doesn't have yet"
+                    >
+                        "• unsaved"
+                    </span>
+                </Show>
+                <span
+                    class="self-center text-sm text-neutral-500"
+                    title="Roughly how much browser storage the list takes up"
+                >
+                    {move || usage.get().map(|bytes| format!("{} stored", storage::format_bytes(bytes)))}
+                </span>
+                <Show when=move || { pending_count() > 0 }>
+                    <span
+                        class="self-center text-xs bg-amber-100 text-amber-800 px-2 py-1 rounded-full"
+                        title=move || {
+                            if online.get() {
+                                "Sending to the server"
+                            } else {
+                                "Offline, these will be sent once back online"
+                            }
+                        }
+                    >
+                        {move || match pending_count() {
+                            1 => "1 change pending".to_string(),
+                            count => format!("{count} changes pending"),
+                        }}
+                    </span>
+                </Show>
+            </div>
+            <Show when=move || import_error.get().is_some()>
+                <p class="mb-4 text-sm text-red-600">
+                    { move || format!("Import failed. {}", import_error.get().unwrap_or_default()) }
+                </p>
+            </Show>
+            <div class="flex gap-2 mb-4">
+                <input
+                    node_ref=select_all_ref
+                    type="checkbox"
+                    title="Select all"
+                    prop:checked=move || all_selected.get()
+                    on:change=move |_| on_select_all()
+                />
+                <input
+                    type="search"
+                    placeholder="Filter items"
+                    class="flex-grow border border-neutral-300 px-2 py-1 rounded"
+                    prop:value=move || query.get()
+                    on:input=move |ev| query.set(event_target_value(&ev))
+                />
+                <select
+                    class="border border-neutral-300 px-2 py-1 rounded"
+                    prop:value=move || sort_param(sort_key.get(), ascending.get())
+                    on:change=move |ev| {
+                        let (key, direction) = parse_sort_param(&event_target_value(&ev));
+                        sort_key.set(key);
+                        ascending.set(direction);
+                    }
+                >
+                    <option value="manual">Manual order</option>
+                    <option value="id-asc">ID, ascending</option>
+                    <option value="id-desc">ID, descending</option>
+                    <option value="value-asc">Value, ascending</option>
+                    <option value="value-desc">Value, descending</option>
+                    <option value="favorites">Favorites first</option>
+                    <option value="favorites-desc">Favorites last</option>
+                    <option value="due">Due soonest</option>
+                    <option value="due-desc">Due latest</option>
+                    <option value="quantity-asc">Quantity, ascending</option>
+                    <option value="quantity-desc">Quantity, descending</option>
+                    <option value="done-asc">Still to do first</option>
+                    <option value="done-desc">Done first</option>
+                    <option value="status-asc">Status, ascending</option>
+                    <option value="status-desc">Status, descending</option>
+                    <option value="created-asc">Oldest first</option>
+                    <option value="created-desc">Newest first</option>
+                </select>
+            </div>
+            <div class="flex gap-2 mb-4">
+                {Completion::ALL
+                    .into_iter()
+                    .map(|filter| {
+                        view! {
+                            <button
+                                class="bg-neutral-200 hover:bg-neutral-300 px-4 py-1 rounded"
+                                class=("font-bold", move || completion.get() == filter)
+                                class=("bg-neutral-300", move || completion.get() == filter)
+                                on:click=move |_| completion.set(filter)
+                            >
+                                { move || format!("{} ({})", filter.label(), completion_count(filter)) }
+                            </button>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+            <Show when=move || available_tags.with(|tags| !tags.is_empty())>
+                <div class="flex flex-wrap gap-2 items-center mb-4 text-sm">
+                    <For
+                        each=move || available_tags.get()
+                        key=|tag| tag.clone()
+                        children=move |tag| {
+                            let label = tag.clone();
+                            let active = {
+                                let tag = tag.clone();
+                                move || tag_filter.with(|tags| tags.contains(&tag))
+                            };
+                            view! {
+                                <button
+                                    class="bg-sky-100 text-sky-800 px-2 rounded-full"
+                                    class=("ring-2", active.clone())
+                                    class=("ring-sky-500", active)
+                                    on:click=move |_| on_filter_tag(tag.clone())
+                                >
+                                    {label}
+                                </button>
+                            }
+                        }
+                    />
+                    <button
+                        class="text-sky-600 font-bold hover:underline disabled:opacity-50 disabled:no-underline"
+                        disabled=move || tag_filter.with(HashSet::is_empty)
+                        on:click=move |_| tag_filter.set(HashSet::new())
+                    >
+                        Clear tags
+                    </button>
+                </div>
+            </Show>
+            <div class="flex gap-2 items-center mb-4 text-sm">
+                {ColorLabel::ALL
+                    .into_iter()
+                    .map(|color| {
+                        view! {
+                            <button
+                                class=format!("w-4 h-4 rounded-full {}", color.class())
+                                class=("ring-2", move || color_filter.get() == Some(color))
+                                class=("ring-offset-1", move || color_filter.get() == Some(color))
+                                class=("ring-neutral-500", move || color_filter.get() == Some(color))
+                                title=color.label()
+                                on:click=move |_| {
+                                    color_filter.update(|filter| {
+                                        *filter = if *filter == Some(color) { None } else { Some(color) };
+                                    });
+                                }
+                            ></button>
+                        }
+                    })
+                    .collect_view()}
+                <button
+                    class="text-sky-600 font-bold hover:underline disabled:opacity-50 disabled:no-underline"
+                    disabled=move || color_filter.get().is_none()
+                    on:click=move |_| color_filter.set(None)
+                >
+                    Any colour
+                </button>
+            </div>
+            <div class="flex gap-2 mb-4 text-sm">
+                <button class="text-sky-600 font-bold hover:underline" on:click=move |_| on_expand_all()>
+                    Expand all
+                </button>
+                <button
+                    class="text-sky-600 font-bold hover:underline"
+                    on:click=move |_| expanded.set(HashSet::new())
+                >
+                    Collapse all
+                </button>
+                <div class="flex ml-auto" role="group" aria-label="View">
+                    <button
+                        class="px-2 rounded-l border border-neutral-300"
+                        class=("bg-neutral-200", move || !table_view.get())
+                        aria-pressed=move || (!table_view.get()).to_string()
+                        on:click=move |_| table_view.set(false)
+                    >
+                        Cards
+                    </button>
+                    <button
+                        class="px-2 rounded-r border border-l-0 border-neutral-300"
+                        class=("bg-neutral-200", move || table_view.get())
+                        aria-pressed=move || table_view.get().to_string()
+                        on:click=move |_| table_view.set(true)
+                    >
+                        Table
+                    </button>
+                </div>
+                <select
+                    class="border border-neutral-300 px-2 rounded"
+                    title="Density"
+                    prop:value=move || preferences.density().get().label()
+                    on:change=move |ev| {
+                        let label = event_target_value(&ev);
+                        if let Some(density) = Density::ALL.into_iter().find(|d| d.label() == label) {
+                            preferences.density().set(density);
+                        }
+                    }
+                >
+                    {Density::ALL
+                        .into_iter()
+                        .map(|density| view! { <option value=density.label()>{density.label()}</option> })
+                        .collect_view()}
+                </select>
+                <label class="flex gap-1 items-center" class=("opacity-50", move || table_view.get())>
+                    <input
+                        type="checkbox"
+                        disabled=move || table_view.get()
+                        prop:checked=move || group_by_letter.get()
+                        on:change=move |_| group_by_letter.update(|group| *group = !*group)
+                    />
+                    Group by letter
+                </label>
+                <label class="flex gap-1 items-center" class=("opacity-50", move || table_view.get())>
+                    <input
+                        type="checkbox"
+                        disabled=move || table_view.get()
+                        prop:checked=move || preferences.markdown().get()
+                        on:change=move |_| preferences.markdown().update(|markdown| *markdown = !*markdown)
+                    />
+                    Markdown
+                </label>
+            </div>
+            <p class="sr-only" role="status" aria-live="polite">{move || announcement.get()}</p>
+            // The rows are the list's own items whilst they're flat. Grouped, each group is a
+            // list of its own, and the table has its own semantics.
+            <div
+                node_ref=list_ref
+                class=move || preferences.density().get().list_class()
+                role=move || (!table_view.get() && !group_by_letter.get()).then_some("list")
+                aria-label="Items"
+            >
+                <Show when=move || is_empty.get()>
+                    <div class="flex flex-col items-center gap-2 py-8 text-neutral-500">
+                        <p>Nothing here yet.</p>
+                        <button
+                            class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded"
+                            on:click=move |_| on_add("Value".to_string())
+                        >
+                            Add your first item
+                        </button>
+                    </div>
+                </Show>
+                // Only one of the table, the groups and the flat list is ever mounted, the cards
+                // being left out altogether whilst the table shows, and grouped or flat otherwise.
+                <Show when=move || table_view.get() && !is_empty.get()>
+                    {table}
+                </Show>
+                <Show when=move || !table_view.get()>
+                    <Show
+                        when=move || !group_by_letter.get()
+                        fallback=move || view! {
+                            <For
+                                each=move || groups.get()
+                                key=|(letter, _)| letter.clone()
+                                children=move |(letter, _)| {
+                                    let ids = {
+                                        let letter = letter.clone();
+                                        Memo::new(move |_| {
+                                            groups.with(|groups| {
+                                                groups
+                                                    .iter()
+                                                    .find(|(l, _)| *l == letter)
+                                                    .map(|(_, ids)| ids.clone())
+                                                    .unwrap_or_default()
+                                            })
+                                        })
+                                    };
+                                    let is_collapsed = {
+                                        let letter = letter.clone();
+                                        move || collapsed.with(|c| c.contains(&letter))
+                                    };
+                                    view! {
+                                        <section class="flex flex-col gap-4">
+                                            <button
+                                                class="flex gap-2 self-start font-bold text-neutral-600"
+                                                aria-expanded={
+                                                    let is_collapsed = is_collapsed.clone();
+                                                    move || (!is_collapsed()).to_string()
+                                                }
+                                                on:click={
+                                                    let letter = letter.clone();
+                                                    move |_| on_collapse(letter.clone())
+                                                }
+                                            >
+                                                <span>{
+                                                    let is_collapsed = is_collapsed.clone();
+                                                    move || if is_collapsed() { "▸" } else { "▾" }
+                                                }</span>
+                                                <span>{letter.clone()}</span>
+                                                <span class="font-normal text-neutral-500">{move || ids.with(Vec::len)}</span>
+                                            </button>
+                                            <Show when=move || !is_collapsed()>
+                                                <div
+                                                    class="flex flex-col gap-4"
+                                                    role="list"
+                                                    aria-label=format!("Items under {letter}")
+                                                >
+                                                    <For each=move || ids.get() key=|id| *id children=row />
+                                                </div>
+                                            </Show>
+                                        </section>
+                                    }
+                                }
+                            />
+                        }
+                    >
+                        <For each=move || rendered.get() key=|id| *id children=row />
+                    </Show>
+                </Show>
+            </div>
+            <Trash trash=store.trash() on_restore on_purge on_empty=on_empty_trash />
+            {move || context_menu.get().map(|(id, x, y)| view! {
+                <ItemMenu
+                    id
+                    x
+                    y
+                    on_edit=on_menu_edit
+                    on_duplicate
+                    on_delete
+                    on_copy
+                    on_close=on_close_menu
+                />
+            })}
+            {
+                // Read untracked, so the dialog isn't rebuilt should the item change whilst open.
+                move || confirming.get().map(|action| {
+                    let (title, message, confirm_label) = match action {
+                        Confirm::Delete(id) => {
+                            let value = store.items().with_untracked(|items| {
+                                items.iter().find(|i| i.id == id).map(|i| i.value.clone())
+                            });
+                            (
+                                "Delete item?".to_string(),
+                                format!(
+                                    "\"{}\" will be moved to the trash.",
+                                    value.unwrap_or_default(),
+                                ),
+                                "Delete",
+                            )
+                        }
+                        Confirm::Clear => (
+                            "Clear all items?".to_string(),
+                            "Every item in the list will be moved to the trash.".to_string(),
+                            "Clear all",
+                        ),
+                        Confirm::Purge(id) => {
+                            let value = store.trash().with_untracked(|trash| {
+                                trash.iter().find(|i| i.id == id).map(|i| i.value.clone())
+                            });
+                            (
+                                "Delete forever?".to_string(),
+                                format!(
+                                    "\"{}\" will be deleted, and can't be restored from the trash.",
+                                    value.unwrap_or_default(),
+                                ),
+                                "Delete forever",
+                            )
+                        }
+                        Confirm::EmptyTrash => (
+                            "Empty the trash?".to_string(),
+                            "Every item in the trash will be deleted forever.".to_string(),
+                            "Empty trash",
+                        ),
+                        Confirm::Reset => (
+                            "Reset to the server's list?".to_string(),
+                            "Your unsaved changes will be discarded, and can't be undone."
+                                .to_string(),
+                            "Reset",
+                        ),
+                    };
+                    view! { <ConfirmDialog title message confirm_label on_confirm on_cancel /> }
+                })
+            }
+        }
+        .into_any())
+    }
+}
+
+/// A collapsible panel listing the items in the trash.
+///
+/// Trashed items can't be edited, so there's no need for per-item Fields here. The For is keyed
+/// by ID and simply renders a copy of each. As ever, the buttons pass raw IDs back up, through
+/// `on_restore` and `on_purge`. All three callbacks are used within the Show's children, so must
+/// be `Send + Sync`.
+#[component]
+fn Trash(
+    #[prop(into)] trash: reactive_stores::Field<Vec<Item>>,
+    on_restore: impl Fn(u128) + Copy + Send + Sync + 'static,
+    on_purge: impl Fn(u128) + Copy + Send + Sync + 'static,
+    on_empty: impl Fn() + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let len = Memo::new(move |_| trash.with(Vec::len));
+
+    view! {
+        <details class="mt-8">
+            <summary class="cursor-pointer text-neutral-500">
+                { move || format!("Trash ({})", len.get()) }
+            </summary>
+            <div class="flex flex-col gap-2 mt-2">
+                <Show
+                    when=move || { len.get() > 0 }
+                    fallback=|| view! { <p class="text-sm text-neutral-500">"The trash is empty."</p> }
+                >
+                    <For
+                        each=move || trash.get()
+                        key=|item| item.id
+                        children=move |item| {
+                            let id = item.id;
+                            view! {
+                                <div class="flex gap-2 items-center">
+                                    <div class="flex-grow text-neutral-500">
+                                        {format!("{} ({})", item.value, id)}
+                                    </div>
+                                    <button
+                                        class="bg-neutral-200 hover:bg-neutral-300 px-4 py-1 rounded"
+                                        on:click=move |_| on_restore(id)
+                                    >
+                                        Restore
+                                    </button>
+                                    <button
+                                        class="bg-neutral-200 hover:bg-neutral-300 px-4 py-1 rounded"
+                                        on:click=move |_| on_purge(id)
+                                    >
+                                        Delete forever
+                                    </button>
+                                </div>
+                            }
+                        }
+                    />
+                    <button
+                        class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded self-start"
+                        on:click=move |_| on_empty()
+                    >
+                        Empty trash
+                    </button>
+                </Show>
+            </div>
+        </details>
+    }
+}
+
+/// A row's context menu, a list of what can be done with the item, opened at `x` and `y`, the
+/// cursor's position within the window.
+///
+/// Whoever renders it decides when it's open, as with `ConfirmDialog`, and each entry calls
+/// `on_close` before its own callback. It also closes by itself, through `on_close`, on a click
+/// anywhere outside it, Escape, or scrolling, which would otherwise leave it hanging over rows
+/// that have moved out from under it. The listeners for those live on the window for as long as
+/// the menu does.
+#[component]
+fn ItemMenu(
+    id: u128,
+    x: i32,
+    y: i32,
+    on_edit: impl Fn(u128) + Copy + 'static,
+    on_duplicate: impl Fn(u128) + Copy + 'static,
+    on_delete: impl Fn(u128) + Copy + 'static,
+    on_copy: impl Fn(u128) + Copy + 'static,
+    on_close: impl Fn() + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let menu_ref = NodeRef::<leptos::html::Div>::new();
+
+    let handle = window_event_listener(ev::pointerdown, move |ev| {
+        let inside = menu_ref.get_untracked().is_some_and(|menu| {
+            ev.target()
+                .and_then(|target| target.dyn_into::<web_sys::Node>().ok())
+                .is_some_and(|target| menu.contains(Some(&target)))
+        });
+        if !inside {
+            on_close();
+        }
+    });
+    on_cleanup(move || handle.remove());
+    let handle = window_event_listener(ev::keydown, move |ev| {
+        if ev.key() == "Escape" {
+            on_close();
+        }
+    });
+    on_cleanup(move || handle.remove());
+    let handle = window_event_listener(ev::scroll, move |_| on_close());
+    on_cleanup(move || handle.remove());
+
+    // Focus starts on the first entry, so the keyboard can carry on from the menu.
+    Effect::new(move |_| {
+        if let Some(button) = menu_ref
+            .get()
+            .and_then(|menu| menu.query_selector("button").ok().flatten())
+            .and_then(|button| button.dyn_into::<web_sys::HtmlElement>().ok())
+        {
+            let _ = button.focus();
+        }
+    });
+
+    view! {
+        <div
+            node_ref=menu_ref
+            role="menu"
+            aria-label="Item actions"
+            class="fixed z-50 flex flex-col py-1 bg-white border border-neutral-300 rounded shadow-lg text-sm"
+            style:left=format!("{x}px")
+            style:top=format!("{y}px")
+        >
+            <button
+                role="menuitem"
+                class="px-4 py-1 text-left hover:bg-neutral-100"
+                on:click=move |_| {
+                    on_close();
+                    on_edit(id);
+                }
+            >
+                Edit
+            </button>
+            <button
+                role="menuitem"
+                class="px-4 py-1 text-left hover:bg-neutral-100"
+                on:click=move |_| {
+                    on_close();
+                    on_duplicate(id);
+                }
+            >
+                Duplicate
+            </button>
+            <button
+                role="menuitem"
+                class="px-4 py-1 text-left hover:bg-neutral-100"
+                on:click=move |_| {
+                    on_close();
+                    on_copy(id);
+                }
+            >
+                Copy value
+            </button>
+            <button
+                role="menuitem"
+                class="px-4 py-1 text-left text-red-600 hover:bg-neutral-100"
+                on:click=move |_| {
+                    on_close();
+                    on_delete(id);
+                }
+            >
+                Delete
+            </button>
+        </div>
+    }
+}
+
+/// An action in `Items` waiting on the user to confirm it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Confirm {
+    Delete(u128),
+    Clear,
+    Purge(u128),
+    EmptyTrash,
+    Reset,
+}
+
+/// A modal asking the user to confirm, or cancel, a destructive action.
+///
+/// Whoever renders the dialog decides when it's open, by rendering it or not. Neither button
+/// closes it by itself, `on_confirm` and `on_cancel` are expected to stop rendering it.
+///
+/// For keyboard and screen reader users the dialog behaves as a modal should:
+///
+/// - Focus moves to Cancel on open, the safer of the two buttons.
+/// - Tab and Shift+Tab cycle between the buttons, rather than escaping to the page behind.
+/// - Escape, or clicking the backdrop, cancels.
+/// - Focus returns to whatever had it beforehand, usually the button that opened the dialog,
+///   once the dialog is removed.
+#[component]
+fn ConfirmDialog(
+    #[prop(into)] title: String,
+    #[prop(into)] message: String,
+    #[prop(into, default = "Confirm".to_string())] confirm_label: String,
+    on_confirm: impl Fn() + 'static,
+    on_cancel: impl Fn() + Copy + 'static,
+) -> impl IntoView {
+    let cancel_ref = NodeRef::<leptos::html::Button>::new();
+    let confirm_ref = NodeRef::<leptos::html::Button>::new();
+
+    // The dialog is only ever opened by a click or keystroke in the browser, so `document()` is
+    // safe to call here. DOM nodes aren't `Send`, hence keeping the element in local storage.
+    let previous = StoredValue::new_local(
+        document()
+            .active_element()
+            .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok()),
+    );
+    on_cleanup(move || {
+        if let Some(Some(element)) = previous.try_get_value() {
+            let _ = element.focus();
+        }
+    });
+
+    Effect::new(move |_| {
+        if let Some(button) = cancel_ref.get() {
+            let _ = button.focus();
+        }
+    });
+
+    // Only the two buttons can take focus, so trapping it means Tab, in either direction, always
+    // moves to whichever button doesn't have it.
+    let on_keydown = move |ev: web_sys::KeyboardEvent| match ev.key().as_str() {
+        "Escape" => {
+            ev.prevent_default();
+            on_cancel();
+        }
+        "Tab" => {
+            let (Some(cancel), Some(confirm)) =
+                (cancel_ref.get_untracked(), confirm_ref.get_untracked())
+            else {
+                return;
+            };
+            ev.prevent_default();
+            let cancel_focused = document().active_element().as_deref() == Some(&**cancel);
+            let _ = if cancel_focused {
+                confirm.focus()
+            } else {
+                cancel.focus()
+            };
+        }
+        _ => {}
+    };
+
+    view! {
+        <div
+            class="fixed inset-0 z-10 flex items-center justify-center bg-black/40"
+            on:click=move |_| on_cancel()
+        >
+            <div
+                role="dialog"
+                aria-modal="true"
+                aria-labelledby="confirm-dialog-title"
+                aria-describedby="confirm-dialog-message"
+                class="flex flex-col gap-4 bg-white rounded shadow-lg p-6 max-w-sm"
+                on:click=|ev| ev.stop_propagation()
+                on:keydown=on_keydown
+            >
+                <h2 id="confirm-dialog-title" class="text-lg font-bold">{title}</h2>
+                <p id="confirm-dialog-message">{message}</p>
+                <div class="flex gap-2 justify-end">
+                    <button
+                        node_ref=cancel_ref
+                        class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded"
+                        on:click=move |_| on_cancel()
+                    >
+                        Cancel
+                    </button>
+                    <button
+                        node_ref=confirm_ref
+                        class="bg-red-600 hover:bg-red-700 text-white px-4 py-2 rounded"
+                        on:click=move |_| on_confirm()
+                    >
+                        {confirm_label}
+                    </button>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+/// The form editing a single item, found by the ID in the path.
+///
+/// Edits are held to the same `VALUE_RULES` as the list's. Saving and deleting go to the server
+/// first, through `update_item` and `delete_item`, and only once it has accepted them are they
+/// written to the shared Store, so navigating back shows them straight away. Should the server
+/// refuse, its error is shown beneath the input and nothing changes. They don't pass through
+/// the list's undo history, which belongs to `Items`.
+#[component]
+fn ItemDetailForm() -> impl IntoView {
+    let store = expect_context::<Store<Data>>();
+    let seed = expect_context::<StoreSeed>();
+    let toasts = use_toasts();
+    let params = use_params_map();
+    let navigate = use_navigate();
+
+    move || {
+        // Nothing until the Resource resolves, as in `Items`.
+        let Some(seeded) = seed.wait() else {
+            return Ok(().into_any());
+        };
+        seeded?;
+        let id = params
+            .read()
+            .get("id")
+            .and_then(|id| id.parse::<u128>().ok());
+
+        // Found untracked, so the form isn't rebuilt by every change to the list.
+        let Some((id, item)) = id.and_then(|id| Some((id, find_field_by_id(store, id)?))) else {
+            return Ok(view! {
+                <p class="mb-4">{ r#"There's no item with that ID."# }</p>
+                <a class="text-sky-600 font-bold hover:underline" href="/">Back to the list</a>
+            }
+            .into_any());
+        };
+
+        let value = RwSignal::new(item.value().get_untracked());
+        let error = RwSignal::new(None::<String>);
+        // Whilst the server's deciding, the buttons are disabled so nothing's sent twice.
+        let busy = RwSignal::new(false);
+
+        // The answer may arrive after the form's gone, should whoever's editing have left
+        // meanwhile. The Store is still updated, as the server has the change, but the signals
+        // are only set with `try_set`, and nobody's navigated back to the list they've left.
+        let save = {
+            let navigate = navigate.clone();
+            move || {
+                let checked = store.items().with_untracked(|items| {
+                    VALUE_RULES.check_edit(items, id, &value.get_untracked())
+                });
+                let (candidate, note) = match checked {
+                    Ok(checked) => checked,
+                    Err(message) => {
+                        error.set(Some(message));
+                        return;
+                    }
+                };
+                busy.set(true);
+                let navigate = navigate.clone();
+                leptos::task::spawn_local(async move {
+                    match update_item(candidate).await {
+                        Ok(saved) => {
+                            let before = snapshot(store);
+                            update_value_by_id(store, id, saved.value);
+                            log_mutation("edit", &before, store);
+                            match note {
+                                Some(note) => toasts.push_toast(note, ToastKind::Info),
+                                None => toasts.push_toast("Item saved", ToastKind::Success),
+                            };
+                            if busy.try_set(false).is_none() {
+                                navigate("/", Default::default());
+                            }
+                        }
+                        Err(err) => {
+                            busy.try_set(false);
+                            error.try_set(Some(format!("Couldn't save: {err}")));
+                        }
+                    }
+                });
+            }
+        };
+
+        let delete = {
+            let navigate = navigate.clone();
+            move || {
+                busy.set(true);
+                let navigate = navigate.clone();
+                leptos::task::spawn_local(async move {
+                    match delete_item(id).await {
+                        Ok(()) => {
+                            let before = snapshot(store);
+                            trash_by_id(store, id);
+                            log_mutation("delete", &before, store);
+                            toasts.push_toast("Item deleted", ToastKind::Success);
+                            if busy.try_set(false).is_none() {
+                                navigate("/", Default::default());
+                            }
+                        }
+                        Err(err) => {
+                            busy.try_set(false);
+                            error.try_set(Some(format!("Couldn't delete: {err}")));
+                        }
+                    }
+                });
+            }
+        };
+
+        Ok::<_, ServerFnError>(view! {
+            <form
+                class="flex flex-col gap-4"
+                on:submit=move |ev| {
+                    ev.prevent_default();
+                    save();
+                }
+            >
+                <p class="text-sm text-neutral-500">{ format!("ID {id}") }</p>
+                <label class="flex flex-col gap-1">
+                    Value
+                    <input
+                        type="text"
+                        class="border border-neutral-300 px-2 py-1 rounded"
+                        prop:value=move || value.get()
+                        on:input=move |ev| {
+                            value.set(event_target_value(&ev));
+                            error.set(None);
+                        }
+                    />
+                    <span class="text-sm text-red-600">{ move || error.get() }</span>
+                </label>
+                <div class="flex gap-2">
+                    <button
+                        type="submit"
+                        class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded disabled:opacity-50"
+                        disabled=move || busy.get()
+                    >
+                        Save
+                    </button>
+                    <button
+                        type="button"
+                        class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded disabled:opacity-50"
+                        disabled=move || busy.get()
+                        on:click=move |_| delete()
+                    >
+                        Delete
+                    </button>
+                    <a class="self-center text-sky-600 font-bold hover:underline" href="/">Cancel</a>
+                </div>
+            </form>
+        }
+        .into_any())
+    }
+}
+
+/// Placeholder rows shown whilst the items load, laid out like `Item` so nothing jumps once they
+/// arrive. `rows` should roughly match how many items are expected.
+#[component]
+fn ItemsSkeleton(#[prop(default = 3)] rows: usize) -> impl IntoView {
+    view! {
+        <div class="flex flex-col gap-4 animate-pulse">
+            {(0..rows)
+                .map(|_| {
+                    view! {
+                        <div class="flex gap-2 items-center">
+                            <div class="size-4 bg-neutral-200 rounded"></div>
+                            <div class="flex-grow h-4 bg-neutral-200 rounded"></div>
+                            <div class="w-16 h-8 bg-neutral-200 rounded"></div>
+                            <div class="w-16 h-8 bg-neutral-200 rounded"></div>
+                        </div>
+                    }
+                })
+                .collect_view()}
+        </div>
+    }
+}
+
+/// Shown in place of the items when `get_items` fails, whether during SSR or after hydrating.
+///
+/// The ErrorBoundary collects every error propagated beneath it, keyed so that each one clears
+/// itself once whatever raised it renders successfully.
+///
+/// Retrying refetches the shared Resource from within the click handler. The Resource lives in
+/// `provide_store`, above this component, so it's reached through the `StoreSeed` context
+/// rather than passed down. Wrapping the refetch in an Action, which awaits the Resource's
+/// next value, gives a `pending` signal to disable the button with whilst it's in flight. A
+/// successful fetch reruns whatever returned the error, which seeds the Store and replaces this
+/// fallback with the items.
+#[component]
+fn LoadError(errors: ArcRwSignal<Errors>) -> impl IntoView {
+    let seed = expect_context::<StoreSeed>();
+    let retry = Action::new(move |_: &()| {
+        seed.items_resource.refetch();
+        async move {
+            let _ = seed.items_resource.await;
+        }
+    });
+
+    view! {
+        <div class="flex flex-col gap-2 p-4 border border-red-300 rounded text-red-600">
+            <p class="font-bold">{ r#"The items couldn't be loaded."# }</p>
+            <ul class="text-sm">
+                { move || {
+                    errors
+                        .get()
+                        .into_iter()
+                        .map(|(_, error)| view! { <li>{ error.to_string() }</li> })
+                        .collect_view()
+                } }
+            </ul>
+            <button
+                class="self-start bg-neutral-200 hover:bg-neutral-300 disabled:opacity-50 px-4 py-2 rounded text-neutral-900"
+                disabled=move || retry.pending().get()
+                on:click=move |_| {
+                    retry.dispatch(());
+                }
+            >
+                { move || if retry.pending().get() { "Retrying…" } else { "Retry" } }
+            </button>
+        </div>
+    }
+}
+
+#[component]
+fn ItemDetail() -> impl IntoView {
+    view! {
+        <Title text="Item Detail"/>
+        <main class="grid justify-center content-center mt-[20vh]">
+            <div class="max-w-xl">
+                <Suspense>
+                    <ErrorBoundary fallback=|errors| view! { <LoadError errors /> }>
+                        <ItemDetailForm />
+                    </ErrorBoundary>
+                </Suspense>
+            </div>
+        </main>
+    }
+}
+
+/// The rules values are held to, by the list on the home page and by the detail page alike.
+const VALUE_RULES: ValueRules = ValueRules {
+    unique_values: UniqueValues::Warn,
+    max_length: Some(80),
+    over_length: OverLength::Block,
+};
+
+#[component]
+fn Home() -> impl IntoView {
+    // `?print=1` swaps the list for `PrintList`. `Items` is only hidden meanwhile, rather than
+    // unmounted, so coming back finds its undo history and everything else just as it was.
+    let query_map = use_query_map();
+    let printing = Memo::new(move |_| query_map.with(|query| query.get_str("print") == Some("1")));
+
+    view! {
+        <Title text="Store Vec Demo"/>
+        <main
+            class="grid justify-center content-center"
+            class=("mt-[20vh]", move || !printing.get())
+        >
+            <div class="max-w-xl">
+                <p class:hidden=move || printing.get() class="mb-16 [&_a]:text-sky-600 [&_a]:font-bold [&_a:hover]:underline">This demo is a reference for how to add, update and delete items from a <a href="https://doc.rust-lang.org/std/vec/struct.Vec.html">Vec</a> inside of a <a href="https://docs.rs/reactive_stores/latest/reactive_stores/struct.Store.html">Store</a> derived from a <a href="https://docs.rs/leptos/latest/leptos/prelude/struct.Resource.html">Resource</a>. { r#"It's"# } suprisingly easy to convolute the reactivity decision tree leading to impenetrable error messages. See also the <a href="/map">HashMap</a>, <a href="/sorted">BTreeMap</a>, <a href="/virtual">virtualized</a>, <a href="/paged">paged</a> and <a href="/streamed">streamed</a> demos.</p>
+                // Suspense component define the boundary of use for any Resource accessed
+                // within. Calling `resource.get()` outside of a Suspense throws a warning.
+                // Calling `resource.get()` within a suspense can be unwrapped.
+                //
+                // See the `provide_store` function's comments for more on Resources.
+                //
+                // The blocking Resource means the fallback never actually shows on a first
+                // page load, but with `Resource::new` it would until `get_items` resolves.
+                <Suspense fallback=|| view! { <ItemsSkeleton /> }>
+                    <ErrorBoundary fallback=|errors| view! { <LoadError errors /> }>
+                        <div class:hidden=move || printing.get()>
+                            <Items max_items=100 rules=VALUE_RULES />
+                        </div>
+                        <Show when=move || printing.get()>
+                            <PrintList />
+                        </Show>
+                    </ErrorBoundary>
+                </Suspense>
+            </div>
+        </main>
+    }
+}
+
+/// The list laid out for printing, or reading, rather than editing. Every item is shown, in the
+/// Store's order and whatever the filters, with its details and sub-items expanded, and there's
+/// nothing to press beyond the buttons at the top, which don't print.
+///
+/// It reads the same Store as `Items`, just down a different render path. Nothing here is keyed,
+/// or reactive below the list as a whole, so any change re-renders the lot, which is fine for a
+/// view nobody edits.
+#[component]
+fn PrintList() -> impl IntoView {
+    let store = expect_context::<Store<Data>>();
+    let seed = expect_context::<StoreSeed>();
+    let query_map = use_query_map();
+    let back = move || {
+        let mut params = query_map.get();
+        params.remove("print");
+        format!("/{}", params.to_query_string())
+    };
+
+    move || {
+        // Nothing until the Resource resolves, as in `Items`.
+        let Some(seeded) = seed.wait() else {
+            return Ok(().into_any());
+        };
+        seeded?;
+
+        let rows = move || {
+            store.items().with(|items| {
+                items
+                    .iter()
+                    .map(|item| {
+                        let details = [
+                            (item.quantity > 0).then(|| format!("× {}", item.quantity)),
+                            Some(item.status.label().to_string()),
+                            item.due.map(|due| format!("Due {}", format_date(due))),
+                            (!item.tags.is_empty()).then(|| item.tags.join(", ")),
+                        ]
+                        .into_iter()
+                        .flatten()
+                        .collect::<Vec<_>>()
+                        .join(" · ");
+                        let children = item
+                            .children
+                            .iter()
+                            .map(|child| view! { <li>{child.value.clone()}</li> })
+                            .collect_view();
+                        view! {
+                            <li class="break-inside-avoid">
+                                <div class="flex gap-2">
+                                    <span>{if item.done { "☑" } else { "☐" }}</span>
+                                    <span class=("line-through", item.done)>
+                                        {item.value.clone()}
+                                        {item.favorite.then_some(" ★")}
+                                    </span>
+                                </div>
+                                <div class="ml-6 text-neutral-500">{details}</div>
+                                {item.description.clone().map(|description| view! {
+                                    <p class="ml-6 whitespace-pre-wrap">{description}</p>
+                                })}
+                                <ul class="ml-10 list-disc">{children}</ul>
+                            </li>
+                        }
+                    })
+                    .collect_view()
+            })
+        };
+        let count = move || match store.items().with(Vec::len) {
+            1 => "1 item".to_string(),
+            count => format!("{count} items"),
+        };
+
+        Ok::<_, ServerFnError>(
+            view! {
+                <div class="flex gap-2 mb-4 print:hidden">
+                    <button
+                        class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded"
+                        on:click=move |_| {
+                            let _ = window().print();
+                        }
+                    >
+                        Print
+                    </button>
+                    <a class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded" href=back>
+                        Back to the list
+                    </a>
+                </div>
+                <h1 class="text-lg font-bold">Items</h1>
+                <p class="mb-4 text-sm text-neutral-500">{count}</p>
+                <ul class="flex flex-col gap-2 text-sm">{rows}</ul>
+            }
+            .into_any(),
+        )
+    }
+}
+
+/// How tightly the list's rows are packed, see `Preferences`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Density {
+    Compact,
+    #[default]
+    Comfortable,
+}
+
+impl Density {
+    /// Every density, in the order they're offered.
+    pub const ALL: [Density; 2] = [Density::Compact, Density::Comfortable];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Density::Compact => "Compact",
+            Density::Comfortable => "Comfortable",
+        }
+    }
+
+    /// The classes for the list's container, spacing the cards and padding the table's cells.
+    fn list_class(&self) -> &'static str {
+        match self {
+            Density::Compact => "flex flex-col gap-1 text-sm [&_td]:py-0",
+            Density::Comfortable => "flex flex-col gap-4 [&_td]:py-2",
+        }
+    }
+}
+
+/// How the list is shown, as opposed to what's in it.
+///
+/// Preferences are kept in a Store of their own, provided by `provide_preferences`, rather
+/// than in `Data`. Nothing that's done to the list should touch them, nor they the list: undo,
+/// import, sharing a link and resetting to the server all replace `Data` wholesale, saving to
+/// the server sends its items, and a change to either Store only notifies what reads that one.
+/// They're the user's own, persisted to this browser alone, see `storage::save_preferences`.
+#[derive(Debug, Clone, Default, PartialEq, Store, Serialize, Deserialize)]
+pub struct Preferences {
+    #[serde(default)]
+    pub density: Density,
+    /// Whether rows render the little Markdown their values may hold, see `markdown::to_html`,
+    /// rather than showing it as written.
+    #[serde(default)]
+    pub markdown: bool,
+}
+
+/// A single item of the demo's list.
+///
+/// # Importing
+///
+/// Items are read back from localStorage and imported from files exported by older builds, and
+/// perhaps newer ones, so deserializing is deliberately forgiving:
+///
+/// - Only `id` and `value` are required. Every other field is optional, taking its default when
+///   missing, as it is from anything saved before that field existed.
+/// - Those optional fields are also `lenient`, so a value that isn't understood, such as a
+///   status some newer build added, takes the default too rather than failing the whole import.
+///   `children` is the exception, as its IDs, like `id`, are too large to pass through `lenient`.
+/// - Unknown fields, perhaps ones since removed, are ignored. Never add
+///   `#[serde(deny_unknown_fields)]`.
+///
+/// Adding a field means giving it `#[serde(default, deserialize_with = "lenient")]`, or else
+/// bumping `storage::DATA_VERSION` and migrating.
+#[derive(Debug, Clone, Default, PartialEq, Store, Patch, Serialize, Deserialize)]
+pub struct Item {
+    /// An item's ID uniquely identifies each item in a keyed list
+    /// such as Leptos' For component. Use an UUID is great way to
+    /// generate random and unique ID's without centralised checking.
+    pub id: u128,
+
+    /// A demonstration example of some data
+    pub value: String,
+
+    /// A count adjusted with the row's steppers, and totalled in the toolbar. Defaults to zero
+    /// for items saved before it existed.
+    #[serde(default, deserialize_with = "lenient")]
+    pub quantity: u32,
+
+    /// Whether the item has been ticked off, turning the demo into a todo list. Also defaults
+    /// for items saved before it existed.
+    #[serde(default, deserialize_with = "lenient")]
+    pub done: bool,
+
+    /// When the item was created, in milliseconds since the Unix epoch. Items saved before it
+    /// existed default to zero, meaning unknown.
+    #[serde(default, deserialize_with = "lenient")]
+    pub created_at: i64,
+
+    /// Free-form labels, kept trimmed and free of duplicates by `Items`.
+    #[serde(default, deserialize_with = "lenient")]
+    pub tags: Vec<String>,
+
+    /// Sub-items nested within this item, keyed by their own IDs just like `Data::items`, which
+    /// gives each its own keyed Field within the item's.
+    #[store(key: u128 = |child| child.id)]
+    #[serde(default)]
+    pub children: Vec<SubItem>,
+
+    /// An optional longer note. `None` is serialized as `null`, and both that and a missing
+    /// field (as in items saved before it existed) deserialize back to `None`.
+    #[serde(default, deserialize_with = "lenient")]
+    pub description: Option<String>,
+
+    /// Where the item is in its workflow, independent of being ticked off as `done`.
+    ///
+    /// The Patch derive can't be applied to an enum, so rather than implementing `PatchField`
+    /// by hand the field tells the derive how to patch it. The closure only runs, and the field
+    /// only notifies, when the new status differs from the old, hence `Status` deriving
+    /// `PartialEq`.
+    #[patch(|this, new| *this = new)]
+    #[serde(default, deserialize_with = "lenient")]
+    pub status: Status,
+
+    /// Whether the item has been starred. Defaults to `false` for items saved before it existed.
+    #[serde(default, deserialize_with = "lenient")]
+    pub favorite: bool,
+
+    /// An optional colour label. Patched in the same way as `status`, for the same reason.
+    #[patch(|this, new| *this = new)]
+    #[serde(default, deserialize_with = "lenient")]
+    pub color: Option<ColorLabel>,
+
+    /// When the item is due, in milliseconds since the Unix epoch at midnight UTC on the day, see
+    /// `time::parse_date`.
+    #[serde(default, deserialize_with = "lenient")]
+    pub due: Option<i64>,
+}
+
+/// An item's workflow stage, see `Item::status`.
+///
+/// A field doesn't need its type to derive Store, as the Store derive on `Item` already gives
+/// `status` a Field of its own. Deriving Store here as well would only add Fields for data held
+/// within variants, of which there's none.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Status {
+    #[default]
+    Todo,
+    InProgress,
+    Done,
+}
+
+impl Status {
+    /// Every status, in order.
+    pub const ALL: [Status; 3] = [Status::Todo, Status::InProgress, Status::Done];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Todo => "Todo",
+            Status::InProgress => "In progress",
+            Status::Done => "Done",
+        }
+    }
+}
+
+/// A named colour an item can be labelled with, see `Item::color`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColorLabel {
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+}
+
+impl ColorLabel {
+    /// Every colour, in the order they're offered.
+    pub const ALL: [ColorLabel; 6] = [
+        ColorLabel::Red,
+        ColorLabel::Orange,
+        ColorLabel::Yellow,
+        ColorLabel::Green,
+        ColorLabel::Blue,
+        ColorLabel::Purple,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ColorLabel::Red => "Red",
+            ColorLabel::Orange => "Orange",
+            ColorLabel::Yellow => "Yellow",
+            ColorLabel::Green => "Green",
+            ColorLabel::Blue => "Blue",
+            ColorLabel::Purple => "Purple",
+        }
+    }
+
+    /// How the colour is written in the URL, see `Items`.
+    pub fn param(&self) -> &'static str {
+        match self {
+            ColorLabel::Red => "red",
+            ColorLabel::Orange => "orange",
+            ColorLabel::Yellow => "yellow",
+            ColorLabel::Green => "green",
+            ColorLabel::Blue => "blue",
+            ColorLabel::Purple => "purple",
+        }
+    }
+
+    /// The Tailwind class filling a dot with this colour. Written out in full, so Tailwind finds
+    /// them when scanning the source.
+    pub fn class(&self) -> &'static str {
+        match self {
+            ColorLabel::Red => "bg-red-500",
+            ColorLabel::Orange => "bg-orange-500",
+            ColorLabel::Yellow => "bg-yellow-400",
+            ColorLabel::Green => "bg-green-500",
+            ColorLabel::Blue => "bg-blue-500",
+            ColorLabel::Purple => "bg-purple-500",
+        }
+    }
+}
+
+/// An item nested within another, see `Item::children`.
+#[derive(Debug, Clone, Default, PartialEq, Store, Patch, Serialize, Deserialize)]
+pub struct SubItem {
+    /// Unique amongst its siblings at least, as the nested For is keyed on it.
+    pub id: u128,
+    pub value: String,
+}
+
+/// The group an item's value is filed under, its first letter in upper case, or "#" for values
+/// that don't start with a letter.
+fn group_letter(value: &str) -> String {
+    match value.trim_start().chars().next() {
+        Some(letter) if letter.is_alphabetic() => letter.to_uppercase().collect(),
+        _ => "#".to_string(),
+    }
+}
+
+/// The DOM ID of an item's row in `Items`, so focus can be moved to it by ID.
+fn row_element_id(id: u128) -> String {
+    format!("row-{id}")
+}
+
+/// Whether `ev` was a click on, or within, a link, which should just follow it rather than
+/// doing whatever else clicking there does.
+fn in_link(ev: &web_sys::MouseEvent) -> bool {
+    ev.target()
+        .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+        .and_then(|element| element.closest("a[href]").ok().flatten())
+        .is_some()
+}
+
+/// Whether the user is typing into a field, in which case keyboard shortcuts should leave the
+/// keystroke alone.
+fn typing() -> bool {
+    document()
+        .active_element()
+        .is_some_and(|element| matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA"))
+}
+
+/// Checks an item is fit to be written to the Store, returning a message explaining why not.
+///
+/// Every path that writes an item's value should validate through here, so the rules live in one
+/// place.
+pub fn validate_item(item: &Item) -> Result<(), String> {
+    if item.value.trim().is_empty() {
+        return Err("Value cannot be empty".to_string());
+    }
+    Ok(())
+}
+
+/// Tidies an item's value as it's written, trimming it and collapsing every run of whitespace
+/// within it, tabs and newlines included, to a single space.
+///
+/// It's the one place values are tidied, so that however an item is added or edited, whether
+/// by a row, the detail page, pasting, importing, splitting, merging or the server fns, its
+/// value comes out the same. The write helpers, such as `update_value_by_id`, call it
+/// themselves, and code creating an item calls it on the value before validating it. The server
+/// normalizes again, having the final say over whatever a client sends.
+///
+/// ```
+/// use my_app::app::normalize_value;
+///
+/// assert_eq!(normalize_value("  milk \t and\n\nbread  "), "milk and bread");
+/// assert_eq!(normalize_value("one  two   three"), "one two three");
+/// assert_eq!(normalize_value(" \t\n "), "");
+/// ```
+pub fn normalize_value(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// How long `value` is, as whoever's typing it would count, that is in characters as they're
+/// seen rather than as they're encoded. An emoji is one however many code points make it up,
+/// a thumbs up with a skin tone say, or a family joined from several people. Against
+/// `max_length` it's the value as it would be saved that's measured, see `normalize_value`.
+///
+/// ```
+/// use my_app::app::value_length;
+///
+/// assert_eq!(value_length("milk"), 4);
+/// assert_eq!(value_length("👍🏽"), 1);
+/// assert_eq!(value_length("👨‍👩‍👧 café"), 6);
+/// ```
+pub fn value_length(value: &str) -> usize {
+    value.graphemes(true).count()
+}
+
+/// Cuts `value` down to at most `max` characters, as `value_length` counts them, so no emoji
+/// is left split part way, and tidies up whatever space that leaves at the end.
+///
+/// ```
+/// use my_app::app::truncate_value;
+///
+/// assert_eq!(truncate_value("oat milk", 4), "oat");
+/// assert_eq!(truncate_value("milk 👍🏽👍🏽", 6), "milk 👍🏽");
+/// assert_eq!(truncate_value("milk", 10), "milk");
+/// ```
+pub fn truncate_value(value: &str, max: usize) -> String {
+    normalize_value(&value.graphemes(true).take(max).collect::<String>())
+}
+
+/// What saving an edited value longer than `ValueRules`' `max_length` does.
+///
+/// `Block` refuses, leaving the value open to be shortened. `Truncate` saves as much as fits,
+/// see `truncate_value`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverLength {
+    #[default]
+    Block,
+    Truncate,
+}
+
+/// What two values are compared by when checking they're unique, see `UniqueValues`. Values
+/// are normalized, as they would be once written, and lowercased, so "Milk" and " milk" are
+/// the same value, as far as anyone reading the list could tell.
+///
+/// ```
+/// use my_app::app::value_key;
+///
+/// assert_eq!(value_key("  Milk "), value_key("milk"));
+/// assert_eq!(value_key("oat  MILK"), "oat milk");
+/// assert_ne!(value_key("milk"), value_key("oat milk"));
+/// ```
+pub fn value_key(value: &str) -> String {
+    normalize_value(value).to_lowercase()
+}
+
+/// Whether `Items` minds an item being added, or edited, to the value another already has, as
+/// compared by `value_key`.
+///
+/// `Warn` says so beneath the input but lets it through, `Block` refuses it. Either way, rows
+/// sharing a value are badged as duplicates, as only adding and editing are checked, and a list
+/// can still come to hold duplicates some other way, such as by importing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UniqueValues {
+    #[default]
+    Off,
+    Warn,
+    Block,
+}
+
+impl UniqueValues {
+    pub fn blocks(&self) -> bool {
+        *self == UniqueValues::Block
+    }
+
+    /// What's shown beneath an input holding a duplicate value.
+    fn message(&self) -> &'static str {
+        match self {
+            UniqueValues::Block => "Another item already has this value, and values must be unique",
+            _ => "Another item already has this value",
+        }
+    }
+
+    fn class(&self) -> &'static str {
+        match self {
+            UniqueValues::Block => "text-sm text-red-600",
+            _ => "text-sm text-amber-600",
+        }
+    }
+}
+
+/// The rules an edited value is held to beyond `validate_item`'s, whether it must be unique
+/// and how long it may be, see `UniqueValues` and `OverLength`. `max_length` counts characters
+/// as `value_length` does.
+///
+/// Both `Items` and the detail page check edits through `check_edit`, so neither saves a value
+/// the other would refuse.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ValueRules {
+    pub unique_values: UniqueValues,
+    pub max_length: Option<usize>,
+    pub over_length: OverLength,
+}
+
+impl ValueRules {
+    /// Checks the item `id` amongst `items` may be edited to `value`, answering with the item
+    /// as it would be saved, its value normalized and perhaps truncated, along with a note for
+    /// whoever's editing should it have been truncated. Or else with why it can't be. Only
+    /// `UniqueValues::Block` refuses a duplicate here, as `Warn` lets it through.
+    ///
+    /// ```
+    /// use my_app::app::{Item, OverLength, UniqueValues, ValueRules};
+    ///
+    /// let items = vec![
+    ///     Item { id: 1, value: "Milk".to_string(), ..Default::default() },
+    ///     Item { id: 2, value: "Bread".to_string(), ..Default::default() },
+    /// ];
+    /// let rules = ValueRules {
+    ///     unique_values: UniqueValues::Block,
+    ///     max_length: Some(8),
+    ///     over_length: OverLength::Truncate,
+    /// };
+    ///
+    /// let (item, note) = rules.check_edit(&items, 2, "  oat   milk ").unwrap();
+    /// assert_eq!((item.value.as_str(), note), ("oat milk", None));
+    /// assert!(rules.check_edit(&items, 1, "MILK").is_ok());
+    /// assert!(rules.check_edit(&items, 2, "MILK").is_err());
+    /// assert!(rules.check_edit(&items, 2, " ").is_err());
+    /// assert!(rules.check_edit(&items, 3, "Eggs").is_err());
+    ///
+    /// let (item, note) = rules.check_edit(&items, 2, "sourdough").unwrap();
+    /// assert_eq!(item.value, "sourdoug");
+    /// assert!(note.is_some());
+    /// ```
+    pub fn check_edit(
+        &self,
+        items: &[Item],
+        id: u128,
+        value: &str,
+    ) -> Result<(Item, Option<String>), String> {
+        let item = items
+            .iter()
+            .find(|item| item.id == id)
+            .ok_or_else(|| "There's no item with that ID".to_string())?;
+        let mut candidate = Item {
+            value: normalize_value(value),
+            ..item.clone()
+        };
+        validate_item(&candidate)?;
+
+        // Truncated first, as it's the value actually saved that mustn't be a duplicate.
+        let mut note = None;
+        if let Some(max) = self
+            .max_length
+            .filter(|max| value_length(&candidate.value) > *max)
+        {
+            match self.over_length {
+                OverLength::Block => return Err(format!("Values can be at most {max} characters")),
+                OverLength::Truncate => {
+                    candidate.value = truncate_value(&candidate.value, max);
+                    note = Some(format!("Only the first {max} characters were saved"));
+                }
+            }
+        }
+
+        let key = value_key(&candidate.value);
+        let taken = items
+            .iter()
+            .any(|other| other.id != id && value_key(&other.value) == key);
+        if self.unique_values.blocks() && taken {
+            return Err(UniqueValues::Block.message().to_string());
+        }
+        Ok((candidate, note))
+    }
+}
+
+/// Deserializes an optional field of `Item`, falling back to its default when the value isn't
+/// understood, rather than failing the whole `Item`, see `Item`'s docs.
+///
+/// The value first goes through a `serde_json::Value`, which holds integers no larger than a
+/// `u64`, so this mustn't be used for IDs.
+fn lenient<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(T::deserialize(value).unwrap_or_default())
+}
+
+/// The keyed Field of the item with the given ID, or `None` if there's no such item, for reading
+/// and writing that one item reactively, as `ItemDetailForm` does.
+///
+/// This is the reading counterpart to the helpers below. The item is found through the keyed
+/// Field's own mapping of IDs to indices, rather than by searching, or cloning, the list, and
+/// the Field goes on finding it by ID however the list changes around it. To check the item is
+/// there it's read untracked the once, so calling this doesn't subscribe to anything. Should
+/// the item be removed later the Field finds nothing, and `get` panics, so use `try_get` and the
+/// like where that could happen.
+///
+/// Writing through the Field writes to the Store:
+///
+/// ```
+/// use leptos::prelude::*;
+/// use my_app::app::{find_field_by_id, Data, DataStoreFields, Item, ItemStoreFields};
+/// use reactive_stores::Store;
+///
+/// let owner = Owner::new();
+/// owner.set();
+/// let store = Store::new(Data::default());
+/// store.items().set(vec![
+///     Item { id: 1, value: "a".to_string(), ..Item::default() },
+///     Item { id: 2, value: "b".to_string(), ..Item::default() },
+/// ]);
+///
+/// let second = find_field_by_id(store, 2).unwrap();
+/// second.value().set("B".to_string());
+/// assert_eq!(store.items().with_untracked(|items| items[1].value.clone()), "B");
+/// assert!(find_field_by_id(store, 3).is_none());
+/// ```
+pub fn find_field_by_id(store: Store<Data>, id: u128) -> Option<reactive_stores::Field<Item>> {
+    let field = AtKeyed::new(store.items(), id);
+    field.try_with_untracked(|_| ())?;
+    Some(field.into())
+}
+
+/// Removes the item with the given ID from the Store, returning it along with the index it was
+/// removed from, or `None` if there's no such item.
+///
+/// This is the delete pattern documented in `Items`, in one place. Taking the ID as a raw copy
+/// means nothing derived from the Store can be held across the write, and the item's position
+/// is found **before** the write begins. The write goes through `store.items()`, so the keyed
+/// Field's mapping of IDs to indices is refreshed for the For.
+///
+/// # Testing
+///
+/// None of the mutation helpers need a browser, only an Owner for the Store to belong to, so
+/// they can be tried out by `cargo test` as below. Each example sets its own Owner before
+/// creating the Store, holding on to it for as long as the Store is used, and reads the results
+/// back untracked as there's nothing to subscribe them.
+///
+/// Here, removing an item shifts those after it down, and a keyed Field for a later item
+/// must still find it afterwards, rather than whichever item now has its old index:
+///
+/// ```
+/// use leptos::prelude::*;
+/// use my_app::app::{remove_by_id, Data, DataStoreFields, Item, ItemStoreFields};
+/// use reactive_stores::{AtKeyed, Store};
+///
+/// let owner = Owner::new();
+/// owner.set();
+/// let store = Store::new(Data::default());
+/// store.items().set(
+///     ["a", "b", "c"]
+///         .into_iter()
+///         .zip(1..)
+///         .map(|(value, id)| Item { id, value: value.to_string(), ..Item::default() })
+///         .collect(),
+/// );
+/// let third = AtKeyed::new(store.items(), 3);
+/// assert_eq!(third.value().get_untracked(), "c");
+///
+/// let (index, removed) = remove_by_id(store, 2).unwrap();
+/// assert_eq!((index, removed.value.as_str()), (1, "b"));
+/// assert_eq!(third.value().get_untracked(), "c");
+/// assert_eq!(store.items().with_untracked(Vec::len), 2);
+/// assert!(remove_by_id(store, 2).is_none());
+/// ```
+pub fn remove_by_id(store: Store<Data>, id: u128) -> Option<(usize, Item)> {
+    let index = store
+        .items()
+        .with_untracked(|items| items.iter().position(|item| item.id == id))?;
+    let mut removed = None;
+    store
+        .items()
+        .update(|items| removed = Some(items.remove(index)));
+    removed.map(|item| (index, item))
+}
+
+/// Logs a mutation of the Store to the console, labelled with what it was and how many items there
+/// were before and after, such as `[mutation] delete: 3 -> 2 items`.
+///
+/// Tracing which handler wrote what is a good start when a write leads to one of the
+/// impenetrable errors described in `Items`. `Items` calls this from `save`, which every mutation
+/// passes through, besides adds, edits and deletes, which are queued instead and so call it
+/// themselves. Logging is only compiled in with the `mutation-log` feature, without it this
+/// does nothing and is inlined away, label and all:
+///
+/// ```sh
+/// cargo leptos watch --features mutation-log
+/// ```
+#[inline(always)]
+pub fn log_mutation(label: &str, before: &Data, store: Store<Data>) {
+    #[cfg(feature = "mutation-log")]
+    {
+        let after = store.items().with_untracked(Vec::len);
+        leptos::logging::log!(
+            "[mutation] {label}: {} -> {after} items",
+            before.items.len()
+        );
+    }
+    #[cfg(not(feature = "mutation-log"))]
+    let _ = (label, before, store);
+}
+
+/// Takes a copy of everything in the Store, without tracking it, for restoring later.
+///
+/// This is only a `Clone` of `Data`, so it's cheap enough to take before every mutation, as
+/// `Items` does for undo.
+pub fn snapshot(store: Store<Data>) -> Data {
+    store.get_untracked()
+}
+
+/// Puts the Store back as it was when `snapshot` was taken, notifying as little as possible.
+///
+/// Undo, import, rolling back a failed save and resetting to the server's state all come back
+/// here, so there's one way of replacing the Store's contents wholesale.
+///
+/// When the snapshot holds the same items in the same order, as after undoing an edit, each item
+/// is patched through its keyed Field, so only the fields that differ notify. Patching the whole
+/// Vec would compare items by position, which is only the same thing while nothing has moved,
+/// see `merge_items`. Otherwise, say an item has been added, deleted or moved since, the list is
+/// replaced. The keyed For still keeps the rows whose IDs remain, but every row re-renders.
+///
+/// Either way, the Store ends up exactly as snapshotted, patched after only edits:
+///
+/// ```
+/// use leptos::prelude::*;
+/// use my_app::app::{restore, snapshot, update_value_by_id, Data, DataStoreFields, Item};
+/// use reactive_stores::Store;
+///
+/// let owner = Owner::new();
+/// owner.set();
+/// let store = Store::new(Data::default());
+/// store.items().set(
+///     ["a", "b", "c"]
+///         .into_iter()
+///         .zip(1..)
+///         .map(|(value, id)| Item { id, value: value.to_string(), ..Item::default() })
+///         .collect(),
+/// );
+/// let items = store.items().get_untracked();
+/// let before = snapshot(store);
+///
+/// update_value_by_id(store, 1, "A".to_string());
+/// update_value_by_id(store, 3, "C".to_string());
+/// restore(store, before);
+/// assert_eq!(store.items().get_untracked(), items);
+/// ```
+///
+/// Or replaced after items have been moved, trashed or added, with the keyed Fields finding
+/// their items again afterwards:
+///
+/// ```
+/// use leptos::prelude::*;
+/// use my_app::app::{
+///     move_item, restore, snapshot, trash_by_id, Data, DataStoreFields, Item, ItemStoreFields,
+/// };
+/// use reactive_stores::{AtKeyed, Store};
+///
+/// let owner = Owner::new();
+/// owner.set();
+/// let store = Store::new(Data::default());
+/// store.items().set(
+///     ["a", "b", "c"]
+///         .into_iter()
+///         .zip(1..)
+///         .map(|(value, id)| Item { id, value: value.to_string(), ..Item::default() })
+///         .collect(),
+/// );
+/// let items = store.items().get_untracked();
+/// let before = snapshot(store);
+///
+/// move_item(store, 0, 2);
+/// trash_by_id(store, 2);
+/// store.items().update(|items| items.push(Item { id: 4, ..Item::default() }));
+/// restore(store, before);
+/// assert_eq!(store.items().get_untracked(), items);
+/// assert!(store.trash().with_untracked(Vec::is_empty));
+/// assert_eq!(AtKeyed::new(store.items(), 3).value().get_untracked(), "c");
+/// ```
+pub fn restore(store: Store<Data>, snapshot: Data) {
+    let Data { items, trash } = snapshot;
+    let same_ids = store.items().with_untracked(|current| {
+        current.len() == items.len() && current.iter().zip(&items).all(|(a, b)| a.id == b.id)
+    });
+    if same_ids {
+        for item in items {
+            AtKeyed::new(store.items(), item.id).patch(item);
+        }
+    } else {
+        store.items().set(items);
+    }
+    // The trash isn't keyed, and is rendered from clones anyway, so it's simply patched.
+    store.trash().patch(trash);
+}
+
+/// Moves the item with the given ID from the list into the trash, returning the index it was
+/// removed from, or `None` if there's no such item.
+pub fn trash_by_id(store: Store<Data>, id: u128) -> Option<usize> {
+    let (index, item) = remove_by_id(store, id)?;
+    store.trash().update(|trash| trash.insert(0, item));
+    Some(index)
+}
+
+/// Moves the item with the given ID out of the trash and back into the list, at `index` (clamped
+/// to the end of the list) if given, otherwise at the end. Returns whether it was restored.
+///
+/// The keyed For relies upon IDs being unique, so should an item with the same ID already be in
+/// the list, say put back via Undo, the trashed copy is left where it is.
+pub fn restore_by_id(store: Store<Data>, id: u128, index: Option<usize>) -> bool {
+    let present = store
+        .items()
+        .with_untracked(|items| items.iter().any(|item| item.id == id));
+    let Some(position) = store
+        .trash()
+        .with_untracked(|trash| trash.iter().position(|item| item.id == id))
+        .filter(|_| !present)
+    else {
+        return false;
+    };
+    let mut restored = None;
+    store
+        .trash()
+        .update(|trash| restored = Some(trash.remove(position)));
+    if let Some(item) = restored {
+        store.items().update(|items| {
+            let index = index.unwrap_or(items.len()).min(items.len());
+            items.insert(index, item);
+        });
+    }
+    true
+}
+
+/// Sets the value of the item with the given ID, returning whether there was such an item.
+///
+/// The find-then-mutate counterpart to `remove_by_id`, following the same rules. Only the
+/// matching item is changed, and its row is kept by the keyed For as its ID is untouched.
+/// The value is normalized first, see `normalize_value`, but validating it is left to the
+/// caller, see `validate_item`.
+///
+/// ```
+/// use leptos::prelude::*;
+/// use my_app::app::{update_value_by_id, Data, DataStoreFields, Item};
+/// use reactive_stores::Store;
+///
+/// let owner = Owner::new();
+/// owner.set();
+/// let store = Store::new(Data::default());
+/// store.items().set(
+///     ["a", "b", "c"]
+///         .into_iter()
+///         .zip(1..)
+///         .map(|(value, id)| Item { id, value: value.to_string(), ..Item::default() })
+///         .collect(),
+/// );
+/// let before = store.items().get_untracked();
+///
+/// assert!(update_value_by_id(store, 2, "  bread  ".to_string()));
+/// let after = store.items().get_untracked();
+/// assert_eq!(after[1], Item { value: "bread".to_string(), ..before[1].clone() });
+/// assert_eq!((&after[0], &after[2]), (&before[0], &before[2]));
+/// assert!(!update_value_by_id(store, 4, "missing".to_string()));
+/// ```
+pub fn update_value_by_id(store: Store<Data>, id: u128, value: String) -> bool {
+    let value = normalize_value(&value);
+    let mut found = false;
+    store.items().update(|items| {
+        if let Some(item) = items.iter_mut().find(|item| item.id == id) {
+            item.value = value;
+            found = true;
+        }
+    });
+    found
+}
+
+/// Merges the item with ID `absorb` into the item with ID `keep`, returning whether both were
+/// found, and were two different items.
+///
+/// The kept item's value has the other's appended, separated by a space, and gains any of its
+/// tags it didn't already have. Everything else is the kept item's own. The absorbed item is
+/// removed in the same write, so the keyed For sees its ID go, and its row with it, in the one
+/// notification, and the kept item's ID, and so its row, is left be. As with `remove_by_id`,
+/// both positions are found before the write begins.
+pub fn merge_by_id(store: Store<Data>, keep: u128, absorb: u128) -> bool {
+    let positions = store.items().with_untracked(|items| {
+        let position = |id: u128| items.iter().position(|item| item.id == id);
+        position(keep).zip(position(absorb))
+    });
+    let Some((keep, absorb)) = positions.filter(|(keep, absorb)| keep != absorb) else {
+        return false;
+    };
+    store.items().update(|items| {
+        let absorbed = items.remove(absorb);
+        let kept = &mut items[if absorb < keep { keep - 1 } else { keep }];
+        kept.value = normalize_value(&format!("{} {}", kept.value, absorbed.value));
+        for tag in absorbed.tags {
+            if !kept.tags.contains(&tag) {
+                kept.tags.push(tag);
+            }
+        }
+    });
+    true
+}
+
+/// The parts an item's value would be split into by `split_by_id`, one per line, or per
+/// semicolon, each normalized, see `normalize_value`, and skipping any left blank.
+pub fn split_value(value: &str) -> Vec<String> {
+    value
+        .split(['\n', ';'])
+        .map(normalize_value)
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+/// Splits `value` into the runs of text that match `query` and those that don't, in order, each
+/// paired with whether it matched, for highlighting search results.
+///
+/// Matching ignores case, as the text filter does, but the runs are cut from `value` itself so
+/// keep their original case. Every occurrence is found, left to right and without overlapping.
+/// An empty `query` matches nothing.
+///
+/// ```
+/// use my_app::app::split_matches;
+///
+/// assert_eq!(
+///     split_matches("Apples and apple pie", "APPLE"),
+///     [
+///         ("Apple".to_string(), true),
+///         ("s and ".to_string(), false),
+///         ("apple".to_string(), true),
+///         (" pie".to_string(), false),
+///     ],
+/// );
+/// assert_eq!(split_matches("Pears", ""), [("Pears".to_string(), false)]);
+/// ```
+pub fn split_matches(value: &str, query: &str) -> Vec<(String, bool)> {
+    // The query is compared a char at a time against each char of the value lowercased, as
+    // lowercasing can change a char's length in bytes, so a match's position in the lowercased
+    // value needn't be its position in the original.
+    let query = query.to_lowercase().chars().collect::<Vec<_>>();
+    let match_len = |rest: &str| {
+        let mut matched = 0;
+        for (index, c) in rest.char_indices() {
+            for lower in c.to_lowercase() {
+                if query.get(matched) != Some(&lower) {
+                    return None;
+                }
+                matched += 1;
+            }
+            if matched == query.len() {
+                return Some(index + c.len_utf8());
+            }
+        }
+        None
+    };
+
+    let mut parts = Vec::new();
+    let mut unmatched = 0;
+    let mut start = 0;
+    while start < value.len() {
+        match match_len(&value[start..]).filter(|_| !query.is_empty()) {
+            Some(len) => {
+                if unmatched < start {
+                    parts.push((value[unmatched..start].to_string(), false));
+                }
+                parts.push((value[start..start + len].to_string(), true));
+                start += len;
+                unmatched = start;
+            }
+            None => start += value[start..].chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    if unmatched < value.len() {
+        parts.push((value[unmatched..].to_string(), false));
+    }
+    parts
+}
+
+/// Splits the item with the given ID into one item per part of its value, see `split_value`,
+/// in its place, returning whether it was split at all, which takes at least two parts.
+///
+/// The first part keeps the original item, ID and all, so its row is kept by the keyed For and
+/// the item is only renamed. Each further part is a copy of it with a fresh ID, as a duplicate
+/// is, inserted after it in order. Reusing the original's ID for any of them would give the For
+/// two rows with the same key. The copies leave behind the description and sub-items, which
+/// stay with the first part. It's all one `batch`, so the list notifies once however many parts
+/// there are.
+pub fn split_by_id(store: Store<Data>, id: u128) -> bool {
+    batch(store, |items| {
+        let Some(index) = items.iter().position(|item| item.id == id) else {
+            return false;
+        };
+        let mut parts = split_value(&items[index].value).into_iter();
+        let (Some(first), rest) = (parts.next(), parts.collect::<Vec<_>>()) else {
+            return false;
+        };
+        if rest.is_empty() {
+            return false;
+        }
+        let copies = rest
+            .into_iter()
+            .map(|value| Item {
+                id: new_id(),
+                value,
+                created_at: now_ms(),
+                description: None,
+                children: Vec::new(),
+                ..items[index].clone()
+            })
+            .collect::<Vec<_>>();
+        items[index].value = first;
+        items.splice(index + 1..index + 1, copies);
+        true
+    })
+}
+
+/// Moves the item at index `from` so it ends up at index `to`, shifting those in between along
+/// by one, all in a single write.
+///
+/// Both indices are clamped to the list, so moving beyond either end leaves the item at that
+/// end, and moving an item to where it already is (or within an empty list) does nothing at
+/// all. The item is removed and reinserted rather than cloned, so its ID, and so its row in the
+/// keyed For, is kept.
+///
+/// See `remove_by_id` for trying out the helpers under `cargo test`:
+///
+/// ```
+/// use leptos::prelude::*;
+/// use my_app::app::{move_item, Data, DataStoreFields, Item};
+/// use reactive_stores::Store;
+///
+/// let owner = Owner::new();
+/// owner.set();
+/// let store = Store::new(Data::default());
+/// store
+///     .items()
+///     .set((1..=4).map(|id| Item { id, ..Item::default() }).collect());
+/// let ids = || store.items().with_untracked(|items| items.iter().map(|item| item.id).collect::<Vec<_>>());
+///
+/// move_item(store, 0, 2);
+/// assert_eq!(ids(), [2, 3, 1, 4]);
+/// move_item(store, 3, 0);
+/// assert_eq!(ids(), [4, 2, 3, 1]);
+/// move_item(store, 1, 99);
+/// assert_eq!(ids(), [4, 3, 1, 2]);
+/// move_item(store, 2, 2);
+/// assert_eq!(ids(), [4, 3, 1, 2]);
+/// ```
+pub fn move_item(store: Store<Data>, from: usize, to: usize) {
+    let len = store.items().with_untracked(Vec::len);
+    if len == 0 {
+        return;
+    }
+    let (from, to) = (from.min(len - 1), to.min(len - 1));
+    if from == to {
+        return;
+    }
+    store.items().update(|items| {
+        let item = items.remove(from);
+        items.insert(to, item);
+    });
+}
+
+/// Makes any number of changes to the list in one write, so the list notifies once, however
+/// many items are added, removed or changed.
+///
+/// Every `store.items().update(...)` notifies everything reading the list, and refreshes the
+/// keyed Field's mapping of IDs to indices. Removing a hundred items one `remove_by_id` at a
+/// time does both a hundred times, where a bulk delete or clear only needs to do them once.
+/// Effects are deferred until the current handler has returned, so the For itself only reruns
+/// once either way, but each write still marks it, and every memo over the list, dirty again,
+/// and each refresh of the mapping is a pass over the whole list.
+///
+/// Batching is the wrong choice for changing a few fields of a few items, such as ticking one
+/// off or patching those the server changed, see `merge_items`. A write to the list notifies
+/// every row, whereas a write through an item's keyed Field only notifies what reads that
+/// field. Reach for this when the list itself changes shape, and for Fields otherwise.
+///
+/// ```
+/// use leptos::prelude::*;
+/// use my_app::app::{batch, Data, DataStoreFields, Item};
+/// use reactive_stores::Store;
+///
+/// let owner = Owner::new();
+/// owner.set();
+/// let store = Store::new(Data::default());
+/// let removed = batch(store, |items| {
+///     items.extend((1..=6).map(|id| Item { id, ..Item::default() }));
+///     let before = items.len();
+///     items.retain(|item| item.id % 2 == 0);
+///     before - items.len()
+/// });
+/// assert_eq!(removed, 3);
+/// assert_eq!(store.items().with_untracked(Vec::len), 3);
+/// ```
+pub fn batch<T>(store: Store<Data>, f: impl FnOnce(&mut Vec<Item>) -> T) -> T {
+    let mut result = None;
+    store.items().update(|items| result = Some(f(items)));
+    result.expect("the update always runs")
+}
+
+/// Which neighbour an item should swap places with when reordered, or which side of a row a
+/// dragged item will be dropped on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+}
+
+/// How imported items are combined with those already in the Store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Swaps the whole list for the imported one.
+    Replace,
+    /// Appends the imported items, each given a fresh ID so none collide with existing keys.
+    Merge,
+}
+
+/// Which items are rendered according to whether they're done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Completion {
+    All,
+    Active,
+    Done,
+}
+
+impl Completion {
+    pub const ALL: [Completion; 3] = [Completion::All, Completion::Active, Completion::Done];
+
+    pub fn matches(&self, item: &Item) -> bool {
+        match self {
+            Completion::All => true,
+            Completion::Active => !item.done,
+            Completion::Done => item.done,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Completion::All => "All",
+            Completion::Active => "Active",
+            Completion::Done => "Done",
+        }
+    }
+
+    /// How the filter is written in the URL, see `Items`.
+    pub fn param(&self) -> &'static str {
+        match self {
+            Completion::All => "all",
+            Completion::Active => "active",
+            Completion::Done => "done",
+        }
+    }
+}
+
+/// What the rendered list is sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    Id,
+    Value,
+    /// Favorites first, otherwise in the Vec's order.
+    Favorite,
+    /// Soonest due first, or latest first descending, with items that aren't due at all last
+    /// either way.
+    Due,
+    Quantity,
+    /// Those still to do first.
+    Done,
+    /// In the order of `Status::ALL`.
+    Status,
+    /// Oldest first.
+    Created,
+}
+
+/// How a sort is written in the sort select's values and in the URL, see `Items`.
+pub fn sort_param(key: Option<SortKey>, ascending: bool) -> &'static str {
+    match (key, ascending) {
+        (None, _) => "manual",
+        (Some(SortKey::Id), true) => "id-asc",
+        (Some(SortKey::Id), false) => "id-desc",
+        (Some(SortKey::Value), true) => "value-asc",
+        (Some(SortKey::Value), false) => "value-desc",
+        (Some(SortKey::Favorite), true) => "favorites",
+        (Some(SortKey::Favorite), false) => "favorites-desc",
+        (Some(SortKey::Due), true) => "due",
+        (Some(SortKey::Due), false) => "due-desc",
+        (Some(SortKey::Quantity), true) => "quantity-asc",
+        (Some(SortKey::Quantity), false) => "quantity-desc",
+        (Some(SortKey::Done), true) => "done-asc",
+        (Some(SortKey::Done), false) => "done-desc",
+        (Some(SortKey::Status), true) => "status-asc",
+        (Some(SortKey::Status), false) => "status-desc",
+        (Some(SortKey::Created), true) => "created-asc",
+        (Some(SortKey::Created), false) => "created-desc",
+    }
+}
+
+/// The inverse of `sort_param`. Anything unrecognised is the manual order.
+pub fn parse_sort_param(param: &str) -> (Option<SortKey>, bool) {
+    match param {
+        "id-asc" => (Some(SortKey::Id), true),
+        "id-desc" => (Some(SortKey::Id), false),
+        "value-asc" => (Some(SortKey::Value), true),
+        "value-desc" => (Some(SortKey::Value), false),
+        "favorites" => (Some(SortKey::Favorite), true),
+        "favorites-desc" => (Some(SortKey::Favorite), false),
+        "due" => (Some(SortKey::Due), true),
+        "due-desc" => (Some(SortKey::Due), false),
+        "quantity-asc" => (Some(SortKey::Quantity), true),
+        "quantity-desc" => (Some(SortKey::Quantity), false),
+        "done-asc" => (Some(SortKey::Done), true),
+        "done-desc" => (Some(SortKey::Done), false),
+        "status-asc" => (Some(SortKey::Status), true),
+        "status-desc" => (Some(SortKey::Status), false),
+        "created-asc" => (Some(SortKey::Created), true),
+        "created-desc" => (Some(SortKey::Created), false),
+        _ => (None, true),
+    }
+}
+
+#[derive(Debug, Clone, Default, Store, Patch, Serialize, Deserialize)]
+pub struct Data {
+    /// It's imperative to use to a Copy type such as u128 for the
+    /// Store's Vec key. Using a String, for example, throws an
+    /// obtuse error when later iterating over the items in a Leptos
+    /// For component.
+    #[store(key: u128 = |item| item.id)]
+    items: Vec<Item>,
+    /// Deleted items, most recently deleted first, until they're restored or deleted forever.
+    /// The trash is local to this browser, `save_items` only ever sends `items`.
+    #[serde(default)]
+    trash: Vec<Item>,
+}
+
+/// A mutation made locally which is yet to reach the server, see `pending` in `Items`.
+///
+/// Each carries what its server fn needs, and nothing more, so the queue can be persisted to
+/// localStorage as is and replayed after a reload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Mutation {
+    Add(Item),
+    Update(Item),
+    Delete(u128),
+}
+
+impl Mutation {
+    /// The ID of the item it mutates.
+    pub fn id(&self) -> u128 {
+        match self {
+            Mutation::Add(item) | Mutation::Update(item) => item.id,
+            Mutation::Delete(id) => *id,
+        }
+    }
+}
+
+/// The changes between one saved list and the next, sent by `save_diff` in place of the whole
+/// list.
+///
+/// Items are compared by ID, and then by value, so an item counts as updated should any of its
+/// fields differ, sub-items included, and only then is it sent, whole. `order` is only sent when
+/// the IDs are no longer in the same order once the additions and deletions are accounted for,
+/// as after moving an item, it otherwise being implied by appending `added` in order.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SaveDiff {
+    pub added: Vec<Item>,
+    pub updated: Vec<Item>,
+    pub deleted: Vec<u128>,
+    pub order: Option<Vec<u128>>,
+}
+
+impl SaveDiff {
+    /// What's changed from `before` to `after`.
+    pub fn between(before: &[Item], after: &[Item]) -> Self {
+        let old = before
+            .iter()
+            .map(|item| (item.id, item))
+            .collect::<HashMap<_, _>>();
+        let new = after.iter().map(|item| item.id).collect::<HashSet<_>>();
+        let mut diff = SaveDiff::default();
+        for item in after {
+            match old.get(&item.id) {
+                None => diff.added.push(item.clone()),
+                Some(saved) if *saved != item => diff.updated.push(item.clone()),
+                Some(_) => {}
+            }
+        }
+        diff.deleted = before
+            .iter()
+            .map(|item| item.id)
+            .filter(|id| !new.contains(id))
+            .collect();
+        // Applied in order, the server's list ends up as those kept, in their old order, followed
+        // by those added. Only should that be wrong is the order sent.
+        let implied = before
+            .iter()
+            .map(|item| item.id)
+            .filter(|id| new.contains(id))
+            .chain(diff.added.iter().map(|item| item.id));
+        if !implied.eq(after.iter().map(|item| item.id)) {
+            diff.order = Some(after.iter().map(|item| item.id).collect());
+        }
+        diff
+    }
+
+    /// Whether there's nothing to send.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.updated.is_empty()
+            && self.deleted.is_empty()
+            && self.order.is_none()
+    }
+}
+
+impl Data {
+    /// Checks every item passes `validate_item`, and that no two share an ID, as the keyed For
+    /// relies upon them being unique.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut ids = HashSet::new();
+        for item in &self.items {
+            validate_item(item).map_err(|error| format!("Item {}: {error}", item.id))?;
+            if !ids.insert(item.id) {
+                return Err(format!("Item {} appears more than once", item.id));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `item` with its value normalized, as the server fns have the final say over whatever a
+/// client sends, see `normalize_value`.
+#[cfg(feature = "ssr")]
+fn normalize_item(item: Item) -> Item {
+    Item {
+        value: normalize_value(&item.value),
+        ..item
+    }
+}
+
+/// Stands in for a database, holding whatever list was last passed to `save_items`, or else the
+/// two items `get_items` first answered with, so the per-item server fns have a list to work on.
+#[cfg(feature = "ssr")]
+static SAVED_ITEMS: std::sync::Mutex<Option<Vec<Item>>> = std::sync::Mutex::new(None);
+
+/// The initial items, IDs and all. Their IDs are generated here, on the server, once, and then
+/// travel with the items to the browser, so the page it hydrates is keyed exactly as the server
+/// rendered it. See `ids::new_id` for why they mustn't be generated whilst rendering.
+#[server]
+pub async fn get_items() -> Result<Vec<Item>, ServerFnError> {
+    let mut saved = SAVED_ITEMS.lock().unwrap();
+    let items = saved.get_or_insert_with(|| {
+        vec![
+            Item {
+                id: new_id(),
+                value: "great".to_string(),
+                quantity: 1,
+                created_at: crate::time::now_ms(),
+                ..Default::default()
+            },
+            Item {
+                id: new_id(),
+                value: "amasing".to_string(),
+                quantity: 2,
+                created_at: crate::time::now_ms(),
+                ..Default::default()
+            },
+        ]
+    });
+    Ok(items.clone())
+}
+
+/// A page of items, as answered by `get_items_page`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page {
+    pub items: Vec<Item>,
+    /// How many items there are altogether, across every page.
+    pub total: u64,
+}
+
+/// How many items `get_items_page` pretends to hold.
+#[cfg(feature = "ssr")]
+const CATALOGUE_LEN: u32 = 1_000;
+
+/// Answers up to `limit` items, starting from the one at `offset`, along with the total.
+///
+/// `get_items` answers every item at once, which is fine for the handful the Vec demo keeps.
+/// This stands in for a backend far too large for that, a catalogue of a thousand items
+/// generated from their index, so both their IDs and the markup hydrating them are the same on
+/// every request. It takes a moment to answer, as a real backend would, and `limit` is capped
+/// so no one request can ask for everything.
+#[server]
+pub async fn get_items_page(offset: u32, limit: u32) -> Result<Page, ServerFnError> {
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let end = offset.saturating_add(limit.min(100)).min(CATALOGUE_LEN);
+    let items = (offset.min(end)..end)
+        .map(|index| Item {
+            id: u128::from(index),
+            value: format!("Item {}", index + 1),
+            ..Default::default()
+        })
+        .collect();
+    Ok(Page {
+        items,
+        total: u64::from(CATALOGUE_LEN),
+    })
+}
+
+/// Streams the same items as `get_items`, one at a time, for the streamed demo.
+///
+/// Each item is sent as its own line of JSON, a serialized `Result<Item, String>`, half a second
+/// apart. Sending errors within the stream, rather than failing it, lets the client tell what
+/// went wrong part way through. `fail_after` does just that, sending an error in place of the
+/// item at that index and then ending the stream.
+#[server(output = StreamingText)]
+pub async fn get_items_stream(fail_after: Option<usize>) -> Result<TextStream, ServerFnError> {
+    use futures::StreamExt;
+
+    let items = get_items().await?;
+    let count = fail_after.map_or(items.len(), |after| after + 1);
+    let lines = futures::stream::iter(items.into_iter().enumerate().take(count)).then(
+        move |(index, item)| async move {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            let line = match fail_after {
+                Some(after) if index >= after => Err(format!("Failed after {after} items")),
+                _ => Ok(item),
+            };
+            serde_json::to_string(&line)
+                .map(|json| json + "\n")
+                .map_err(ServerFnError::new)
+        },
+    );
+    Ok(TextStream::new(lines))
+}
+
+/// Replaces the saved list with `items`, each normalized and validated first as `add_item`
+/// does, the whole list being refused should any item be invalid.
+#[server]
+pub async fn save_items(items: Vec<Item>) -> Result<(), ServerFnError> {
+    let items = items.into_iter().map(normalize_item).collect::<Vec<_>>();
+    for item in &items {
+        validate_item(item).map_err(ServerFnError::new)?;
+    }
+    *SAVED_ITEMS.lock().unwrap() = Some(items);
+    Ok(())
+}
+
+/// Applies the changes since the last save to the saved list, sparing a save from sending the
+/// whole list, see `SaveDiff`.
+///
+/// Every change is applied as an upsert or an idempotent delete, so a diff overlapping changes
+/// the server already has, such as those `add_item` or `delete_item` have sent since, does no
+/// harm. Items in `order` the server doesn't have are skipped, and any it has that `order`
+/// misses are kept at the end. Should the server have no saved list to apply it to, the client
+/// is asked for the whole list instead.
+///
+/// The server fn's own struct is renamed, as by default it would be `SaveDiff` too.
+#[server(name = SaveDiffArgs)]
+pub async fn save_diff(diff: SaveDiff) -> Result<(), ServerFnError> {
+    let diff = SaveDiff {
+        added: diff.added.into_iter().map(normalize_item).collect(),
+        updated: diff.updated.into_iter().map(normalize_item).collect(),
+        ..diff
+    };
+    for item in diff.added.iter().chain(&diff.updated) {
+        validate_item(item).map_err(ServerFnError::new)?;
+    }
+    let mut saved = SAVED_ITEMS.lock().unwrap();
+    let Some(items) = saved.as_mut() else {
+        return Err(ServerFnError::new(
+            "There's no saved list to apply the changes to",
+        ));
+    };
+    items.retain(|item| !diff.deleted.contains(&item.id));
+    for item in diff.added.into_iter().chain(diff.updated) {
+        match items.iter_mut().find(|saved| saved.id == item.id) {
+            Some(saved) => *saved = item,
+            None => items.push(item),
+        }
+    }
+    if let Some(order) = diff.order {
+        let mut unordered = std::mem::take(items);
+        for id in order {
+            if let Some(index) = unordered.iter().position(|item| item.id == id) {
+                items.push(unordered.remove(index));
+            }
+        }
+        items.append(&mut unordered);
+    }
+    Ok(())
+}
+
+/// Adds a single item to the end of the list, or replaces the item with the same ID should the
+/// server already have it. Adding the same item twice is harmless, as a replayed mutation might.
+#[server]
+pub async fn add_item(item: Item) -> Result<(), ServerFnError> {
+    let item = normalize_item(item);
+    validate_item(&item).map_err(ServerFnError::new)?;
+    if let Some(items) = SAVED_ITEMS.lock().unwrap().as_mut() {
+        match items.iter_mut().find(|saved| saved.id == item.id) {
+            Some(saved) => *saved = item,
+            None => items.push(item),
+        }
+    }
+    Ok(())
+}
+
+/// Updates a single item, answering with the server's canonical version of it.
+///
+/// The value is normalized, see `normalize_value`, and validated, so the server has the final
+/// say whichever client sent it. An item the server doesn't know, as it's never been saved, is
+/// still normalized and returned.
+#[server]
+pub async fn update_item(item: Item) -> Result<Item, ServerFnError> {
+    let item = normalize_item(item);
+    validate_item(&item).map_err(ServerFnError::new)?;
+    if let Some(items) = SAVED_ITEMS.lock().unwrap().as_mut() {
+        if let Some(saved) = items.iter_mut().find(|saved| saved.id == item.id) {
+            *saved = item.clone();
+        }
+    }
+    Ok(item)
+}
+
+/// Deletes a single item, sparing a delete from having to send the whole list to `save_items`.
+///
+/// Deleting is idempotent. An ID the server doesn't know, perhaps as it's already been deleted
+/// or was never saved, is treated as a success, since either way the item is gone.
+#[server]
+pub async fn delete_item(id: u128) -> Result<(), ServerFnError> {
+    if let Some(items) = SAVED_ITEMS.lock().unwrap().as_mut() {
+        items.retain(|item| item.id != id);
+    }
+    Ok(())
+}
+
+/// The mutation helpers run against a Store, without a browser. The doctests above show each
+/// one on its own, these check them together, and especially that keyed Fields, which rows
+/// hold on to, still find their items once the list around them has changed.
+///
+/// Every test creates an Owner for its Store to belong to and keeps it until the test ends, see
+/// `store_of`. Results are read untracked, as nothing is subscribed.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A Store holding an item per value, their IDs counting up from 1, and the Owner it
+    /// belongs to, which must be held for as long as the Store is used.
+    fn store_of(values: &[&str]) -> (Owner, Store<Data>) {
+        let owner = Owner::new();
+        owner.set();
+        let store = Store::new(Data::default());
+        store.items().set(
+            values
+                .iter()
+                .zip(1..)
+                .map(|(value, id)| Item {
+                    id,
+                    value: value.to_string(),
+                    ..Item::default()
+                })
+                .collect(),
+        );
+        (owner, store)
+    }
+
+    fn values(store: Store<Data>) -> Vec<String> {
+        store
+            .items()
+            .with_untracked(|items| items.iter().map(|item| item.value.clone()).collect())
+    }
+
+    #[test]
+    fn adding_leaves_keyed_fields_on_their_items() {
+        let (_owner, store) = store_of(&["a", "b"]);
+        let second = AtKeyed::new(store.items(), 2);
+
+        store.items().update(|items| {
+            items.insert(
+                0,
+                Item {
+                    id: 3,
+                    value: "c".to_string(),
+                    ..Item::default()
+                },
+            )
+        });
+
+        assert_eq!(values(store), ["c", "a", "b"]);
+        assert_eq!(second.value().get_untracked(), "b");
+        assert_eq!(
+            find_field_by_id(store, 3).map(|item| item.value().get_untracked()),
+            Some("c".to_string())
+        );
+    }
+
+    #[test]
+    fn restoring_from_the_trash_adds_the_item_back_where_it_was() {
+        let (_owner, store) = store_of(&["a", "b", "c"]);
+
+        let index = trash_by_id(store, 2);
+        assert_eq!(values(store), ["a", "c"]);
+        assert!(restore_by_id(store, 2, index));
+
+        assert_eq!(values(store), ["a", "b", "c"]);
+        assert!(store.trash().with_untracked(Vec::is_empty));
+        // Restoring again would add a second item with the same ID.
+        assert!(!restore_by_id(store, 2, index));
+    }
+
+    #[test]
+    fn updating_changes_only_that_item() {
+        let (_owner, store) = store_of(&["a", "b", "c"]);
+        let second = AtKeyed::new(store.items(), 2);
+
+        assert!(update_value_by_id(
+            store,
+            2,
+            " bread\tand  butter ".to_string()
+        ));
+
+        assert_eq!(values(store), ["a", "bread and butter", "c"]);
+        assert_eq!(second.value().get_untracked(), "bread and butter");
+        assert!(!update_value_by_id(store, 4, "missing".to_string()));
+        assert_eq!(values(store), ["a", "bread and butter", "c"]);
+    }
+
+    #[test]
+    fn removing_leaves_keyed_fields_on_their_items() {
+        let (_owner, store) = store_of(&["a", "b", "c"]);
+        let third = AtKeyed::new(store.items(), 3);
+
+        let (index, removed) = remove_by_id(store, 1).unwrap();
+
+        assert_eq!((index, removed.value.as_str()), (0, "a"));
+        assert_eq!(values(store), ["b", "c"]);
+        // The pitfall `Items` documents: read by index, the Field would now find nothing.
+        assert_eq!(third.value().get_untracked(), "c");
+        assert!(remove_by_id(store, 1).is_none());
+    }
+
+    #[test]
+    fn moving_leaves_keyed_fields_on_their_items() {
+        let (_owner, store) = store_of(&["a", "b", "c"]);
+        let first = AtKeyed::new(store.items(), 1);
+
+        move_item(store, 0, 2);
+
+        assert_eq!(values(store), ["b", "c", "a"]);
+        assert_eq!(first.value().get_untracked(), "a");
+        first.value().set("A".to_string());
+        assert_eq!(values(store), ["b", "c", "A"]);
+    }
 }
@@ -0,0 +1,146 @@
+use super::{get_items, Item};
+use crate::ids::new_id;
+use leptos::prelude::*;
+use leptos_meta::Title;
+use reactive_stores::Store;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A single row of the map demo.
+///
+/// Without a keyed Field to hand down (see `DataMap`), the row is instead given its ID and a
+/// read-only signal of its value. Callbacks pass raw IDs back up, just as in the Vec demo.
+#[component]
+pub(super) fn ItemMap(
+    id: u128,
+    #[prop(into)] value: Signal<String>,
+    on_mutate: impl Fn(u128) + Copy + 'static,
+    on_delete: impl Fn(u128) + Copy + 'static,
+) -> impl IntoView {
+    view! {
+        <div class="flex gap-2">
+            <div class="flex-grow">{ move || format!("{} ({})", value.get(), id) }</div>
+            <button
+                class="bg-neutral-200 hover:bg-neutral-300 px-4 py-1 rounded"
+                on:click=move |_| on_mutate(id)
+            >Mutate</button>
+            <button
+                class="bg-neutral-200 hover:bg-neutral-300 px-4 py-1 rounded"
+                on:click=move |_| on_delete(id)
+            >Delete</button>
+        </div>
+    }
+}
+
+#[component]
+fn ItemsMap() -> impl IntoView {
+    // The same blocking Resource as the Vec demo, see the `Items` component for more.
+    let items_resource =
+        Resource::new_blocking(|| (), move |_| async { get_items().await.unwrap() });
+
+    move || {
+        let store = Store::new(DataMap {
+            items: items_resource
+                .get()
+                .unwrap()
+                .into_iter()
+                .map(|item| (item.id, item))
+                .collect(),
+        });
+
+        // Iterating a HashMap yields its entries in an arbitrary order, one which may change
+        // entirely as entries are inserted or removed. Handed straight to a keyed For, rows would
+        // jump around the page after every mutation.
+        //
+        // So the IDs are sorted (by value, then by ID to break ties) before rendering. The memo
+        // only notifies the For when the resulting order actually changes.
+        let ids = Memo::new(move |_| {
+            let mut entries = store.items().with(|items| {
+                items
+                    .values()
+                    .map(|item| (item.value.clone(), item.id))
+                    .collect::<Vec<_>>()
+            });
+            entries.sort();
+            entries.into_iter().map(|(_, id)| id).collect::<Vec<_>>()
+        });
+
+        // With a map, lookups and removals are by key with no searching for a position first.
+        // The ID is still a raw copy, so nothing derived from the map is held across the write.
+        let on_mutate = move |id: u128| {
+            store.items().update(|items| {
+                if let Some(item) = items.get_mut(&id) {
+                    item.value = "Mutated".to_string();
+                }
+            });
+        };
+        let on_delete = move |id: u128| {
+            store.items().update(|items| {
+                items.remove(&id);
+            });
+        };
+
+        view! {
+            <div class="flex gap-2 mb-4">
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded"
+                    on:click=move |_| {
+                        store.items().update(move |items| {
+                            let id = new_id();
+                            items.insert(id, Item {
+                                id,
+                                value: "Value".to_string(),
+                                created_at: crate::time::now_ms(),
+                                ..Default::default()
+                            });
+                        });
+                    }
+                >
+                    Add
+                </button>
+            </div>
+            <div class="flex flex-col gap-4">
+                <For
+                    each=move || ids.get()
+                    key=|id| *id
+                    children=move |id| {
+                        // A deleted entry's row may read its value once more before the For
+                        // removes it, so fall back to an empty value rather than unwrapping.
+                        let value = Signal::derive(move || {
+                            store
+                                .items()
+                                .with(|items| items.get(&id).map(|item| item.value.clone()))
+                                .unwrap_or_default()
+                        });
+                        view! { <ItemMap id value on_mutate on_delete /> }
+                    }
+                />
+            </div>
+        }
+    }
+}
+
+#[component]
+pub fn HomeMap() -> impl IntoView {
+    view! {
+        <Title text="Store HashMap Demo"/>
+        <main class="grid justify-center content-center mt-[20vh]">
+            <div class="max-w-xl">
+                <p class="mb-16 [&_a]:text-sky-600 [&_a]:font-bold [&_a:hover]:underline">The same demo, only with items kept in a <a href="https://doc.rust-lang.org/std/collections/struct.HashMap.html">HashMap</a> keyed by ID rather than a Vec. Back to the <a href="/">Vec demo</a>.</p>
+                <Suspense>
+                    <ItemsMap />
+                </Suspense>
+            </div>
+        </main>
+    }
+}
+
+#[derive(Debug, Clone, Store, Serialize, Deserialize)]
+pub struct DataMap {
+    /// `#[store(key: ...)]` only applies to collections indexed by position, such as a Vec,
+    /// which is what lets the Vec demo hand each row its own `Field<Item>`. A map gets no such
+    /// per-entry Fields, so entries are looked up by ID through `store.items()` instead.
+    ///
+    /// reactive_stores has no `Patch` support for maps either, hence only deriving `Store`.
+    items: HashMap<u128, Item>,
+}
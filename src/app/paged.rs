@@ -0,0 +1,285 @@
+use super::{
+    get_items_page, map::ItemMap, remove_by_id, update_value_by_id, Data, DataStoreFields,
+    ItemStoreFields, ItemsSkeleton, Page,
+};
+use leptos::{either::Either, prelude::*};
+use leptos_meta::Title;
+use leptos_router::hooks::use_query_map;
+use reactive_stores::{AtKeyed, Store};
+use std::collections::HashSet;
+use wasm_bindgen::{closure::Closure, JsCast};
+
+/// How many items are fetched, and shown, at a time.
+const PAGE_SIZE: u32 = 10;
+
+/// Renders one page of `get_items_page` at a time, the page number coming from `?page=`.
+///
+/// # Refetching
+///
+/// The Resource's source is the page number, read from the query string. Following a link to
+/// another page changes the query, and so the page number, which reruns the fetcher for that
+/// page. The Memo only notifies when the number actually changes, so any other change to the
+/// query doesn't refetch.
+///
+/// # Blocking
+///
+/// The Resource blocks, as the Vec demo's does, so whichever page is asked for is rendered on
+/// the server and hydrated without a second request. With `Resource::new` instead, the first
+/// response would be sent without the items, the skeleton showing until the browser had
+/// fetched them itself.
+///
+/// Either way, later pages are only ever fetched by the browser. Within a Transition, rather
+/// than a Suspense, the current page stays put while the next loads, dimmed, instead of being
+/// swapped for the skeleton on every click.
+///
+/// # The Store
+///
+/// Each page is its own small Store, created afresh from the Resource's latest value just as in
+/// the map demo. A page's rows only live as long as the page, so there's nothing to carry over.
+/// The pagination follows the server's total, not the length of the page.
+#[component]
+fn ItemsPaged() -> impl IntoView {
+    let query = use_query_map();
+    let page = Memo::new(move |_| {
+        query.with(|query| {
+            query
+                .get_str("page")
+                .and_then(|page| page.parse::<u32>().ok())
+                .unwrap_or(1)
+                .max(1)
+        })
+    });
+    let page_resource = Resource::new_blocking(
+        move || page.get(),
+        // `page` is whatever the URL says, so a huge one mustn't overflow the offset. Saturated,
+        // it's simply past the end, and answered with an empty page.
+        |page| get_items_page((page - 1).saturating_mul(PAGE_SIZE), PAGE_SIZE),
+    );
+    let loading = RwSignal::new(false);
+
+    view! {
+        <Transition fallback=|| view! { <ItemsSkeleton /> } set_pending=loading>
+            {move || page_resource.get().map(|result| match result {
+                Ok(Page { items, total }) => {
+                    let pages = total.div_ceil(u64::from(PAGE_SIZE)).max(1);
+                    let current = u64::from(page.get_untracked());
+                    let store = Store::new(Data {
+                        items,
+                        trash: Vec::new(),
+                    });
+                    let on_mutate = move |id: u128| {
+                        update_value_by_id(store, id, "Mutated".to_string());
+                    };
+                    let on_delete = move |id: u128| {
+                        remove_by_id(store, id);
+                    };
+                    // Past either end there's no link, only a greyed out label.
+                    let link = move |label: &'static str, to: u64| {
+                        if (1..=pages).contains(&to) && to != current {
+                            Either::Left(view! {
+                                <a
+                                    class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded"
+                                    href=format!("?page={to}")
+                                >
+                                    {label}
+                                </a>
+                            })
+                        } else {
+                            Either::Right(view! {
+                                <span class="bg-neutral-200 opacity-50 px-4 py-2 rounded">
+                                    {label}
+                                </span>
+                            })
+                        }
+                    };
+                    Either::Left(view! {
+                        <div
+                            class="flex flex-col gap-4 transition-opacity"
+                            class:opacity-50=move || loading.get()
+                        >
+                            <For
+                                each=move || store.items().with(|items| items.iter().map(|item| item.id).collect::<Vec<_>>())
+                                key=|id| *id
+                                children=move |id| {
+                                    let item = AtKeyed::new(store.items(), id);
+                                    let value = Signal::derive(move || item.value().get());
+                                    view! { <ItemMap id value on_mutate on_delete /> }
+                                }
+                            />
+                        </div>
+                        <div class="flex gap-2 mt-4">
+                            {link("Previous", current.saturating_sub(1))}
+                            <span class="self-center text-sm text-neutral-500">
+                                {format!("Page {current} of {pages} · {total} items")}
+                            </span>
+                            {link("Next", current + 1)}
+                        </div>
+                    })
+                }
+                Err(error) => Either::Right(view! {
+                    <p class="text-sm text-red-600" role="alert">
+                        {format!("The page couldn't be loaded. {error}")}
+                    </p>
+                }),
+            })}
+        </Transition>
+    }
+}
+
+/// Renders `get_items_page` a page at a time too, only appending each page to the list as the
+/// bottom of it scrolls into view, rather than replacing the last.
+///
+/// An IntersectionObserver watches an empty sentinel div beneath the rows, keeping `visible` up
+/// to date. Loading is then driven by an Effect, rather than by the observer itself. The
+/// observer only reports the sentinel entering or leaving view, so a page too short to push it
+/// back out of view would otherwise be the last one loaded. The Effect instead reruns once each
+/// load finishes, and carries on loading for as long as the sentinel is still in view, until
+/// the server's total is reached.
+///
+/// Pages are appended to the end of the one Store for as long as the component lives. The For
+/// is keyed by ID, so appending only creates rows for the new items and leaves the rest be. The
+/// next page's offset is that of the items fetched so far, not the length of the list, as rows
+/// can be deleted locally, and any item already in the list is skipped so its key isn't
+/// repeated, say were the server's list to shift between pages.
+///
+/// Nothing is fetched on the server. The list renders empty there, and the first page is
+/// loaded once the browser has hydrated and the observer first sees the sentinel.
+#[component]
+fn ItemsInfinite() -> impl IntoView {
+    let store = Store::new(Data::default());
+    let total = RwSignal::new(None::<u64>);
+    let fetched = RwSignal::new(0_u32);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+    let visible = RwSignal::new(false);
+    let exhausted = move || {
+        total
+            .get()
+            .is_some_and(|total| u64::from(fetched.get()) >= total)
+    };
+
+    let load_more = move || {
+        loading.set(true);
+        let offset = fetched.get_untracked();
+        leptos::task::spawn_local(async move {
+            let result = get_items_page(offset, PAGE_SIZE).await;
+            // Everything here goes with the component, so should it have gone, there's nowhere
+            // to put the page.
+            if loading.try_set(false).is_none() {
+                return;
+            }
+            match result {
+                Ok(Page {
+                    items,
+                    total: count,
+                }) => {
+                    fetched.update(|fetched| *fetched += items.len() as u32);
+                    total.set(Some(count));
+                    store.items().update(|list| {
+                        let known = list.iter().map(|item| item.id).collect::<HashSet<_>>();
+                        list.extend(items.into_iter().filter(|item| !known.contains(&item.id)));
+                    });
+                }
+                Err(failure) => error.set(Some(failure.to_string())),
+            }
+        });
+    };
+    // An error stops loading until it's retried, rather than retrying on every rerun.
+    Effect::new(move |_| {
+        if visible.get() && !loading.get() && error.with(Option::is_none) && !exhausted() {
+            load_more();
+        }
+    });
+
+    // The observer belongs to the Effect that creates it once the sentinel is mounted, and is
+    // disconnected when that Effect is cleaned up, as it is when the component unmounts.
+    // Neither the observer nor its callback are `Send`, hence keeping them in local storage,
+    // which lives as long as the cleanup that needs it.
+    let sentinel = NodeRef::<leptos::html::Div>::new();
+    Effect::new(move |_| {
+        let Some(node) = sentinel.get() else {
+            return;
+        };
+        let callback = Closure::<dyn Fn(js_sys::Array)>::new(move |entries: js_sys::Array| {
+            if let Some(entry) = entries.iter().last() {
+                let entry = entry.unchecked_into::<web_sys::IntersectionObserverEntry>();
+                visible.set(entry.is_intersecting());
+            }
+        });
+        let Ok(observer) = web_sys::IntersectionObserver::new(callback.as_ref().unchecked_ref())
+        else {
+            return;
+        };
+        observer.observe(&node);
+        let observer = StoredValue::new_local((observer, callback));
+        on_cleanup(move || {
+            observer.try_with_value(|(observer, _)| observer.disconnect());
+        });
+    });
+
+    let on_mutate = move |id: u128| {
+        update_value_by_id(store, id, "Mutated".to_string());
+    };
+    let on_delete = move |id: u128| {
+        remove_by_id(store, id);
+    };
+
+    view! {
+        <div class="flex flex-col gap-4">
+            <For
+                each=move || store.items().with(|items| items.iter().map(|item| item.id).collect::<Vec<_>>())
+                key=|id| *id
+                children=move |id| {
+                    let item = AtKeyed::new(store.items(), id);
+                    let value = Signal::derive(move || item.value().get());
+                    view! { <ItemMap id value on_mutate on_delete /> }
+                }
+            />
+        </div>
+        <div node_ref=sentinel class="h-px"></div>
+        <div class="mt-4 text-sm text-neutral-500">
+            {move || {
+                if let Some(failure) = error.get() {
+                    Either::Left(view! {
+                        <p class="text-red-600" role="alert">
+                            {format!("The next page couldn't be loaded. {failure}")}
+                            " "
+                            <button
+                                class="text-sky-600 font-bold hover:underline"
+                                on:click=move |_| error.set(None)
+                            >
+                                Try again
+                            </button>
+                        </p>
+                    })
+                } else if loading.get() || total.get().is_none() {
+                    Either::Right("Loading…".to_string())
+                } else if exhausted() {
+                    Either::Right(format!("All {} items loaded", fetched.get()))
+                } else {
+                    Either::Right(String::new())
+                }
+            }}
+        </div>
+    }
+}
+
+/// The paged demo, or with `?mode=infinite` the infinite scroll one.
+#[component]
+pub fn HomePaged() -> impl IntoView {
+    let query = use_query_map();
+    let infinite =
+        Memo::new(move |_| query.with(|query| query.get_str("mode") == Some("infinite")));
+
+    view! {
+        <Title text="Store Paged Demo"/>
+        <main class="grid justify-center content-center mt-[20vh]">
+            <div class="max-w-xl">
+                <p class="mb-16 [&_a]:text-sky-600 [&_a]:font-bold [&_a:hover]:underline">A thousand items on the server, fetched ten at a time, with the pagination driven by the total the server answers with. Show them in <a href="/paged">pages</a> or with <a href="/paged?mode=infinite">infinite scroll</a>. Back to the <a href="/">Vec demo</a>.</p>
+                <Show when=move || infinite.get() fallback=|| view! { <ItemsPaged /> }>
+                    <ItemsInfinite />
+                </Show>
+            </div>
+        </main>
+    }
+}
@@ -0,0 +1,113 @@
+use super::{get_items, map::ItemMap, Item};
+use crate::ids::new_id;
+use leptos::prelude::*;
+use leptos_meta::Title;
+use reactive_stores::Store;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Inserts an item keyed by its own ID.
+///
+/// In the Vec demo `#[store(key: u128 = |item| item.id)]` derives each key from the item itself,
+/// so the two can never disagree. A map stores its key alongside the item instead, and nothing
+/// stops `items.insert(some_other_id, item)` or a later write to `item.id` from leaving an entry
+/// whose key no longer matches its item. Keep all inserts going through here, and never write
+/// to an `id` in place, so the key derivation lives in one spot just as it does with the
+/// attribute.
+fn insert(items: &mut BTreeMap<u128, Item>, item: Item) {
+    items.insert(item.id, item);
+}
+
+#[component]
+fn ItemsSorted() -> impl IntoView {
+    // The same blocking Resource as the Vec demo, see the `Items` component for more.
+    let items_resource =
+        Resource::new_blocking(|| (), move |_| async { get_items().await.unwrap() });
+
+    move || {
+        let mut items = BTreeMap::new();
+        for item in items_resource.get().unwrap() {
+            insert(&mut items, item);
+        }
+        let store = Store::new(DataSorted { items });
+
+        // A BTreeMap always iterates in key order, so unlike the HashMap demo there's no sort to
+        // perform. A newly inserted item simply appears in its sorted position, and the keyed For
+        // moves no other row to make room for it.
+        let ids = Memo::new(move |_| {
+            store
+                .items()
+                .with(|items| items.keys().copied().collect::<Vec<_>>())
+        });
+
+        let on_mutate = move |id: u128| {
+            store.items().update(|items| {
+                if let Some(item) = items.get_mut(&id) {
+                    item.value = "Mutated".to_string();
+                }
+            });
+        };
+        let on_delete = move |id: u128| {
+            store.items().update(|items| {
+                items.remove(&id);
+            });
+        };
+
+        view! {
+            <div class="flex gap-2 mb-4">
+                <button
+                    class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded"
+                    on:click=move |_| {
+                        store.items().update(move |items| {
+                            insert(items, Item {
+                                id: new_id(),
+                                value: "Value".to_string(),
+                                created_at: crate::time::now_ms(),
+                                ..Default::default()
+                            });
+                        });
+                    }
+                >
+                    Add
+                </button>
+            </div>
+            <div class="flex flex-col gap-4">
+                <For
+                    each=move || ids.get()
+                    key=|id| *id
+                    children=move |id| {
+                        // See the HashMap demo for why this doesn't unwrap.
+                        let value = Signal::derive(move || {
+                            store
+                                .items()
+                                .with(|items| items.get(&id).map(|item| item.value.clone()))
+                                .unwrap_or_default()
+                        });
+                        view! { <ItemMap id value on_mutate on_delete /> }
+                    }
+                />
+            </div>
+        }
+    }
+}
+
+#[component]
+pub fn HomeSorted() -> impl IntoView {
+    view! {
+        <Title text="Store BTreeMap Demo"/>
+        <main class="grid justify-center content-center mt-[20vh]">
+            <div class="max-w-xl">
+                <p class="mb-16 [&_a]:text-sky-600 [&_a]:font-bold [&_a:hover]:underline">The same demo, only with items kept in a <a href="https://doc.rust-lang.org/std/collections/struct.BTreeMap.html">BTreeMap</a>, which keeps them sorted by ID. Back to the <a href="/">Vec demo</a>.</p>
+                <Suspense>
+                    <ItemsSorted />
+                </Suspense>
+            </div>
+        </main>
+    }
+}
+
+#[derive(Debug, Clone, Store, Serialize, Deserialize)]
+pub struct DataSorted {
+    /// As with `DataMap`, a map field gets no per-entry Fields, only a deterministic order.
+    items: BTreeMap<u128, Item>,
+}
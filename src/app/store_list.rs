@@ -0,0 +1,198 @@
+use super::{
+    log_mutation, normalize_value, remove_by_id, snapshot, update_value_by_id, AddItem, Data,
+    DataStoreFields, Item,
+};
+use crate::ids::new_id;
+use leptos::prelude::*;
+use reactive_stores::{AtKeyed, Field, Store};
+use std::hash::Hash;
+
+/// Shown beneath an empty list, unless it's loading or has failed.
+#[slot]
+pub struct Empty {
+    children: ChildrenFn,
+}
+
+/// Shown beneath the rows whilst `when` is true, such as whilst more are being fetched.
+#[slot]
+pub struct Loading {
+    #[prop(into)]
+    when: Signal<bool>,
+    children: ChildrenFn,
+}
+
+/// Shown beneath the rows whilst there's an `error`, which the children can read too.
+#[slot]
+pub struct Failed {
+    #[prop(into)]
+    error: Signal<Option<String>>,
+    children: ChildrenFn,
+}
+
+/// What a row can ask of the list, each callback taking the raw ID of the row's item.
+///
+/// These are `Callback`s, rather than the `impl Fn(u128) + Copy` the rest of the demo hands
+/// down, only as a render prop can't be generic over each of them. They're just as cheap to
+/// copy into a row, and are called with `run`.
+#[derive(Clone, Copy)]
+pub struct RowCallbacks {
+    pub on_mutate: Callback<u128>,
+    pub on_delete: Callback<u128>,
+}
+
+/// The Store's list in a keyed For, each row rendered by `row`, with the add, delete and mutate
+/// plumbing done here once.
+///
+/// `row` is given the item's keyed Field, so it can read, or write, whichever fields it likes
+/// individually, and the `RowCallbacks`. Just as throughout the demo, rows pass raw IDs back
+/// up rather than anything derived from the Store, and the callbacks do the writing, through
+/// `remove_by_id` and `update_value_by_id`, so their rules are followed whatever the row's
+/// markup. `addable` adds an `AddItem` form above the rows.
+///
+/// Those writes, and adding, are logged through `log_mutation`, but are otherwise only writes
+/// to the Store. Nothing here keeps an undo history, shows a toast or tells the server, which
+/// are all `Items`' own, so a page wanting any of them owns them too, such as by giving its
+/// rows callbacks of its own in place of `RowCallbacks`. The streamed demo wants none, its list
+/// being only ever streamed in.
+///
+/// The `empty`, `loading` and `failed` slots are all optional, and at most one shows, the
+/// failure first. They're rendered beneath the rows, not in place of them, so a list that fails
+/// part way keeps what it has.
+///
+/// ```ignore
+/// <StoreList store key=|item: &Item| item.id row=|item, callbacks| view! { <MyRow item callbacks /> }>
+///     <Empty slot>Nothing yet</Empty>
+///     <Loading slot when=loading>Loading…</Loading>
+/// </StoreList>
+/// ```
+///
+/// # Keys
+///
+/// `key` decides which row is which, the For keeping a row for as long as its item gives the
+/// same key, and rendering a fresh one whenever the key changes. Usually that's the item's ID,
+/// but it needn't be. Keying on `(item.id, item.done)`, say, would render a ticked off item's
+/// row afresh, running whatever it does on mount, rather than updating it in place. However
+/// it's keyed, each row still reads and writes its item through the Field keyed by ID that
+/// `Data`'s `#[store(key)]` gives it, so keys must be unique amongst the items just as IDs are.
+///
+/// The key must be `Copy`, as `Data::items`' own key is, for the reason given there, a `String`
+/// key leading to an obtuse error once iterated in a For. Here it's refused up front instead,
+/// at the `key` prop, as the bound isn't satisfied:
+///
+/// ```compile_fail
+/// use leptos::prelude::*;
+/// use my_app::app::{store_list::{RowCallbacks, StoreList}, Data, Item};
+/// use reactive_stores::{Field, Store};
+///
+/// fn list(store: Store<Data>) -> impl IntoView {
+///     view! {
+///         <StoreList
+///             store
+///             key=|item: &Item| item.value.clone()
+///             row={|item: Field<Item>, _: RowCallbacks| view! { <p>{move || item.get().value}</p> }}
+///         />
+///     }
+/// }
+/// ```
+///
+/// Whereas keying on anything `Copy`, such as the ID, is fine:
+///
+/// ```
+/// use leptos::prelude::*;
+/// use my_app::app::{store_list::{RowCallbacks, StoreList}, Data, Item};
+/// use reactive_stores::{Field, Store};
+///
+/// fn list(store: Store<Data>) -> impl IntoView {
+///     view! {
+///         <StoreList
+///             store
+///             key=|item: &Item| item.id
+///             row={|item: Field<Item>, _: RowCallbacks| view! { <p>{move || item.get().value}</p> }}
+///         />
+///     }
+/// }
+/// ```
+///
+/// `Items` doesn't use this, needing far more of its rows than a render prop could sensibly
+/// carry, but the streamed demo does.
+#[component]
+pub fn StoreList<KF, K, R, V>(
+    store: Store<Data>,
+    key: KF,
+    row: R,
+    #[prop(optional)] addable: bool,
+    #[prop(optional)] empty: Option<Empty>,
+    #[prop(optional)] loading: Option<Loading>,
+    #[prop(optional)] failed: Option<Failed>,
+) -> impl IntoView
+where
+    KF: Fn(&Item) -> K + Send + Sync + 'static,
+    K: Copy + Eq + Hash + Send + Sync + 'static,
+    R: Fn(Field<Item>, RowCallbacks) -> V + Clone + Send + Sync + 'static,
+    V: IntoView + 'static,
+{
+    let callbacks = RowCallbacks {
+        on_mutate: Callback::new(move |id: u128| {
+            let before = snapshot(store);
+            update_value_by_id(store, id, "Mutated".to_string());
+            log_mutation("mutate", &before, store);
+        }),
+        on_delete: Callback::new(move |id: u128| {
+            let before = snapshot(store);
+            remove_by_id(store, id);
+            log_mutation("delete", &before, store);
+        }),
+    };
+    let on_add = move |value: String| {
+        let before = snapshot(store);
+        store.items().update(|items| {
+            items.push(Item {
+                id: new_id(),
+                value: normalize_value(&value),
+                ..Default::default()
+            })
+        });
+        log_mutation("add", &before, store);
+    };
+    // Each row's key is paired with its item's ID, as it's the ID that finds the row's Field.
+    let keys = Memo::new(move |_| {
+        store.items().with(|items| {
+            items
+                .iter()
+                .map(|item| (key(item), item.id))
+                .collect::<Vec<_>>()
+        })
+    });
+
+    let empty = empty.map(|empty| empty.children);
+    let loading = loading.map(|loading| (loading.when, loading.children));
+    let failed = failed.map(|failed| (failed.error, failed.children));
+    let status = move || {
+        if let Some((error, children)) = &failed {
+            if error.with(Option::is_some) {
+                return Some(children());
+            }
+        }
+        if let Some((when, children)) = &loading {
+            if when.get() {
+                return Some(children());
+            }
+        }
+        match &empty {
+            Some(children) if keys.with(Vec::is_empty) => Some(children()),
+            _ => None,
+        }
+    };
+
+    view! {
+        {addable.then(|| view! { <AddItem on_add /> })}
+        <div class="flex flex-col gap-4">
+            <For
+                each=move || keys.get()
+                key=|(key, _)| *key
+                children=move |(_, id)| row(AtKeyed::new(store.items(), id).into(), callbacks)
+            />
+        </div>
+        {status}
+    }
+}
@@ -0,0 +1,179 @@
+use super::{
+    get_items_stream,
+    map::ItemMap,
+    store_list::{Empty, Failed, Loading, StoreList},
+    Data, DataStoreFields, Item, ItemStoreFields,
+};
+use futures::StreamExt;
+use leptos::prelude::*;
+use leptos_meta::Title;
+use reactive_stores::Store;
+
+/// How streaming the items is going.
+#[derive(Debug, Clone, PartialEq)]
+enum Progress {
+    Streaming,
+    Done,
+    Failed(String),
+}
+
+/// Takes every complete line from `buffer`, leaving any partial line at the end in place.
+///
+/// The stream's chunks are however the network happened to split the response, not one per
+/// item. A chunk may hold several lines, or end part way through one, so lines are only parsed
+/// once their newline has arrived.
+fn take_lines(buffer: &mut String) -> Vec<String> {
+    let Some(end) = buffer.rfind('\n') else {
+        return Vec::new();
+    };
+    let rest = buffer.split_off(end + 1);
+    std::mem::replace(buffer, rest)
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+/// Appends items to the Store as `get_items_stream` sends them, until it ends or fails.
+///
+/// `current` is checked before every write, and once it's false the stream is abandoned, as a
+/// newer one has replaced it or the component, Store and all, has gone. Whatever was appended
+/// before an error is kept.
+async fn stream_into(
+    store: Store<Data>,
+    fail_after: Option<usize>,
+    current: impl Fn() -> bool,
+) -> Result<(), String> {
+    let mut stream = get_items_stream(fail_after)
+        .await
+        .map_err(|error| error.to_string())?
+        .into_inner();
+    let mut buffer = String::new();
+    while let Some(chunk) = stream.next().await {
+        if !current() {
+            return Ok(());
+        }
+        buffer.push_str(&chunk.map_err(|error| error.to_string())?);
+        for line in take_lines(&mut buffer) {
+            let item = serde_json::from_str::<Result<Item, String>>(&line)
+                .map_err(|error| error.to_string())??;
+            // The keyed For relies upon IDs being unique, so an item sent twice is only added
+            // once.
+            store.items().update(|items| {
+                if !items.iter().any(|existing| existing.id == item.id) {
+                    items.push(item);
+                }
+            });
+        }
+    }
+    if buffer.trim().is_empty() {
+        Ok(())
+    } else {
+        Err("The stream ended part way through an item".to_string())
+    }
+}
+
+/// Renders items as they're streamed in, rather than waiting for the whole list.
+///
+/// The Vec demo's blocking Resource holds the page back until every item has loaded. Here the
+/// Store starts out empty, on the server and the client alike so hydration matches, and once
+/// hydrated an Effect starts reading `get_items_stream`. Each item is pushed onto the list as
+/// its line arrives, so the keyed For adds one row at a time and leaves the rest be.
+///
+/// The rows, and whatever's shown beneath them, are left to `StoreList`. An error part way
+/// through, whether sent by the server or the connection dropping, stops the stream and is
+/// shown beneath the rows that made it, which are kept. Streaming again starts afresh. Should
+/// that happen before the last stream has finished, the old one is abandoned, as each is
+/// numbered in `run` and only the latest may write to the Store.
+#[component]
+fn ItemsStreamed() -> impl IntoView {
+    let store = Store::new(Data::default());
+    let progress = RwSignal::new(Progress::Streaming);
+    let fail = RwSignal::new(false);
+    let run = StoredValue::new(0_u64);
+
+    let start = move || {
+        let this = run.get_value() + 1;
+        run.set_value(this);
+        store.items().set(Vec::new());
+        progress.set(Progress::Streaming);
+        let fail_after = fail.get_untracked().then_some(1);
+        leptos::task::spawn_local(async move {
+            let current = move || run.try_get_value() == Some(this);
+            let outcome = stream_into(store, fail_after, current).await;
+            if current() {
+                progress.set(match outcome {
+                    Ok(()) => Progress::Done,
+                    Err(error) => Progress::Failed(error),
+                });
+            }
+        });
+    };
+    Effect::new(move |_| start());
+
+    let streaming = Signal::derive(move || progress.get() == Progress::Streaming);
+    let error = Signal::derive(move || match progress.get() {
+        Progress::Failed(error) => Some(error),
+        _ => None,
+    });
+
+    view! {
+        <div class="flex gap-2 mb-4">
+            <button
+                class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded"
+                on:click=move |_| start()
+            >
+                Stream again
+            </button>
+            <label class="self-center flex gap-2 items-center text-sm">
+                <input type="checkbox" bind:checked=fail />
+                Fail part way
+            </label>
+            <span class="self-center text-sm text-neutral-500">
+                {move || match progress.get() {
+                    Progress::Streaming => "Streaming…".to_string(),
+                    Progress::Done => format!("{} items", store.items().with(Vec::len)),
+                    Progress::Failed(_) => "Stopped".to_string(),
+                }}
+            </span>
+        </div>
+        <StoreList
+            store
+            key=|item: &Item| item.id
+            row=|item, callbacks| {
+                let id = item.id().get_untracked();
+                let value = Signal::derive(move || item.value().get());
+                let on_mutate = move |id| callbacks.on_mutate.run(id);
+                let on_delete = move |id| callbacks.on_delete.run(id);
+                view! { <ItemMap id value on_mutate on_delete /> }
+            }
+        >
+            <Empty slot>
+                <p class="text-sm text-neutral-500">Nothing was streamed.</p>
+            </Empty>
+            <Loading slot when=streaming>
+                <p class="mt-4 text-sm text-neutral-500">"More on the way…"</p>
+            </Loading>
+            <Failed slot error>
+                <p class="mt-4 text-sm text-red-600" role="alert">
+                    {move || format!("Streaming failed. {}", error.get().unwrap_or_default())}
+                </p>
+            </Failed>
+        </StoreList>
+    }
+}
+
+/// `SsrMode::InOrder` sends the page in order, holding back whatever follows a blocking
+/// Resource until it's ready. There's none here, so the whole page is sent straight away and the
+/// items follow over the stream, rather than the response waiting on the slowest of them.
+#[component]
+pub fn HomeStreamed() -> impl IntoView {
+    view! {
+        <Title text="Store Streamed Demo"/>
+        <main class="grid justify-center content-center mt-[20vh]">
+            <div class="max-w-xl">
+                <p class="mb-16 [&_a]:text-sky-600 [&_a]:font-bold [&_a:hover]:underline">The same items, streamed from a server function one at a time and each rendered as it arrives, rather than all at once. Back to the <a href="/">Vec demo</a>.</p>
+                <ItemsStreamed />
+            </div>
+        </main>
+    }
+}
@@ -0,0 +1,155 @@
+use leptos::prelude::*;
+use std::time::Duration;
+
+/// How long a toast is shown before dismissing itself.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Error,
+}
+
+/// A toast's optional button, such as Undo.
+#[derive(Clone)]
+struct ToastAction {
+    label: String,
+    on_action: Callback<()>,
+}
+
+#[derive(Clone)]
+struct Toast {
+    key: u64,
+    message: String,
+    kind: ToastKind,
+    action: Option<ToastAction>,
+}
+
+/// A handle onto the toasts shown by the `ToastHost`, found with [`use_toasts`].
+///
+/// Handlers run outside of any component's owner, so context can't be looked up from within
+/// them. Look the handle up once while setting up a component instead, it's `Copy`, and push
+/// toasts through it from wherever:
+///
+/// ```ignore
+/// let toasts = use_toasts();
+/// let on_add = move |value: String| {
+///     // ...
+///     toasts.push_toast("Item added", ToastKind::Success);
+/// };
+/// ```
+#[derive(Clone, Copy)]
+pub struct Toasts {
+    toasts: RwSignal<Vec<Toast>>,
+    next_key: StoredValue<u64>,
+}
+
+impl Toasts {
+    /// Shows a toast, which dismisses itself after a few seconds. Returns its key, for
+    /// dismissing it sooner.
+    pub fn push_toast(self, message: impl Into<String>, kind: ToastKind) -> u64 {
+        self.push(message.into(), kind, None)
+    }
+
+    /// As [`Toasts::push_toast`], with a button labelled `label`. Clicking it calls `on_action`
+    /// and dismisses the toast.
+    pub fn push_toast_with_action(
+        self,
+        message: impl Into<String>,
+        kind: ToastKind,
+        label: impl Into<String>,
+        on_action: impl Fn() + Send + Sync + 'static,
+    ) -> u64 {
+        let action = ToastAction {
+            label: label.into(),
+            on_action: Callback::new(move |()| on_action()),
+        };
+        self.push(message.into(), kind, Some(action))
+    }
+
+    pub fn dismiss(self, key: u64) {
+        // The timeout may fire after the host has gone, hence `try_update`.
+        self.toasts
+            .try_update(|toasts| toasts.retain(|toast| toast.key != key));
+    }
+
+    fn push(self, message: String, kind: ToastKind, action: Option<ToastAction>) -> u64 {
+        let key = self.next_key.get_value();
+        self.next_key.set_value(key + 1);
+        self.toasts.update(|toasts| {
+            toasts.push(Toast {
+                key,
+                message,
+                kind,
+                action,
+            })
+        });
+        set_timeout(move || self.dismiss(key), TIMEOUT);
+        key
+    }
+}
+
+/// Creates the app's one `Toasts` and provides it via context, for `ToastHost` to show.
+///
+/// Called from `App`, ahead of `provide_store`, so that even the Store's persistence, which
+/// lives above every component, can push toasts.
+pub fn provide_toasts() -> Toasts {
+    let toasts = Toasts {
+        toasts: RwSignal::new(Vec::new()),
+        next_key: StoredValue::new(0),
+    };
+    provide_context(toasts);
+    toasts
+}
+
+/// Finds the `Toasts` provided by `provide_toasts`.
+pub fn use_toasts() -> Toasts {
+    expect_context::<Toasts>()
+}
+
+/// Shows whichever toasts have been pushed, stacked in the bottom right corner, newest last.
+/// Clicking a toast dismisses it.
+///
+/// There's one host at the root of the app, so toasts outlive the page that pushed them.
+#[component]
+pub fn ToastHost(children: Children) -> impl IntoView {
+    let toasts = use_toasts();
+
+    view! {
+        {children()}
+        <div class="fixed bottom-4 right-4 flex flex-col gap-2" aria-live="polite">
+            <For
+                each=move || toasts.toasts.get()
+                key=|toast| toast.key
+                children=move |toast| {
+                    let Toast { key, message, kind, action } = toast;
+                    let colour = match kind {
+                        ToastKind::Info => "bg-neutral-800",
+                        ToastKind::Success => "bg-green-700",
+                        ToastKind::Error => "bg-red-700",
+                    };
+                    view! {
+                        <div
+                            class=format!(
+                                "flex gap-4 items-center text-white px-4 py-2 rounded shadow cursor-pointer {colour}",
+                            )
+                            role=if kind == ToastKind::Error { "alert" } else { "status" }
+                            on:click=move |_| toasts.dismiss(key)
+                        >
+                            <span>{message}</span>
+                            {action.map(|ToastAction { label, on_action }| view! {
+                                <button
+                                    class="text-sky-300 font-bold hover:underline"
+                                    on:click=move |_| on_action.run(())
+                                >
+                                    {label}
+                                </button>
+                            })}
+                        </div>
+                    }
+                }
+            />
+        </div>
+    }
+}
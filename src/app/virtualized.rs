@@ -0,0 +1,135 @@
+use super::{
+    map::ItemMap, remove_by_id, update_value_by_id, Data, DataStoreFields, Item, ItemStoreFields,
+};
+use crate::ids::new_id;
+use leptos::prelude::*;
+use leptos_meta::Title;
+use reactive_stores::{AtKeyed, Store};
+
+/// Renders only the rows within view of the scroll container, plus `overscan` rows either side.
+///
+/// Every row is `row_height` pixels tall, so which rows are in view follows directly from the
+/// container's scroll offset without measuring anything. An inner div is sized to the height of
+/// the whole list, preserving the scrollbar, and the mounted rows are shifted down to where they
+/// would have been.
+///
+/// The Store is only ever read here. Writes go back up through the raw-ID callbacks, as with the
+/// other components. These are used within the For's children, which must be `Send + Sync`.
+#[component]
+fn VirtualItems(
+    store: Store<Data>,
+    #[prop(default = 40.0)] row_height: f64,
+    #[prop(default = 480.0)] height: f64,
+    #[prop(default = 5)] overscan: usize,
+    on_mutate: impl Fn(u128) + Copy + Send + Sync + 'static,
+    on_delete: impl Fn(u128) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let scroll_top = RwSignal::new(0.0);
+
+    // Both memos read the length, so adding or deleting an item updates the total height and the
+    // visible window alike.
+    let len = Memo::new(move |_| store.items().with(|items| items.len()));
+    let window = Memo::new(move |_| {
+        let first = ((scroll_top.get() / row_height).floor() as usize).saturating_sub(overscan);
+        let count = (height / row_height).ceil() as usize + overscan * 2;
+        store.items().with(|items| {
+            let first = first.min(items.len());
+            let last = (first + count).min(items.len());
+            let ids = items[first..last]
+                .iter()
+                .map(|item| item.id)
+                .collect::<Vec<_>>();
+            (first, ids)
+        })
+    });
+
+    view! {
+        <div
+            class="overflow-y-auto border border-neutral-300 rounded"
+            style:height=format!("{height}px")
+            on:scroll=move |ev| {
+                scroll_top.set(event_target::<web_sys::Element>(&ev).scroll_top() as f64);
+            }
+        >
+            <div style:height=move || format!("{}px", len.get() as f64 * row_height)>
+                <div style:transform=move || {
+                    format!("translateY({}px)", window.with(|(first, _)| *first) as f64 * row_height)
+                }>
+                    <For
+                        each=move || window.get().1
+                        key=|id| *id
+                        children=move |id| {
+                            let item = AtKeyed::new(store.items(), id);
+                            let value = Signal::derive(move || item.value().get());
+                            view! {
+                                <div class="px-2" style:height=format!("{row_height}px")>
+                                    <ItemMap id value on_mutate on_delete />
+                                </div>
+                            }
+                        }
+                    />
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn ItemsVirtual() -> impl IntoView {
+    // IDs are derived from each item's position, rather than generated at random, so that the
+    // server and the client produce the same list and hydration matches.
+    let store = Store::new(Data {
+        items: (0..10_000)
+            .map(|index| Item {
+                id: index,
+                value: format!("Item {index}"),
+                ..Default::default()
+            })
+            .collect(),
+        trash: Vec::new(),
+    });
+
+    let on_mutate = move |id: u128| {
+        update_value_by_id(store, id, "Mutated".to_string());
+    };
+    let on_delete = move |id: u128| {
+        remove_by_id(store, id);
+    };
+
+    view! {
+        <div class="flex gap-2 mb-4">
+            <button
+                class="bg-neutral-200 hover:bg-neutral-300 px-4 py-2 rounded"
+                on:click=move |_| {
+                    store.items().update(move |items| {
+                        items.insert(0, Item {
+                            id: new_id(),
+                            value: "Value".to_string(),
+                            created_at: crate::time::now_ms(),
+                            ..Default::default()
+                        });
+                    });
+                }
+            >
+                Add
+            </button>
+            <span class="self-center text-sm text-neutral-500">
+                { move || format!("{} items", store.items().with(|items| items.len())) }
+            </span>
+        </div>
+        <VirtualItems store on_mutate on_delete />
+    }
+}
+
+#[component]
+pub fn HomeVirtual() -> impl IntoView {
+    view! {
+        <Title text="Store Virtualized Demo"/>
+        <main class="grid justify-center content-center mt-[20vh]">
+            <div class="max-w-xl">
+                <p class="mb-16 [&_a]:text-sky-600 [&_a]:font-bold [&_a:hover]:underline">Ten thousand items, of which only those scrolled into view are ever mounted. Back to the <a href="/">Vec demo</a>.</p>
+                <ItemsVirtual />
+            </div>
+        </main>
+    }
+}
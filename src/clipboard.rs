@@ -0,0 +1,28 @@
+use leptos::prelude::window;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+/// Writes `text` to the clipboard through the async Clipboard API, or explains why it couldn't
+/// in a message fit for a toast. Client only, so only call it from handlers.
+///
+/// The API only exists in secure contexts, so `navigator.clipboard` is missing altogether from a
+/// page served over plain HTTP from anywhere but localhost. Calling through it regardless would
+/// throw, hence checking for it first. Where it does exist the browser may still refuse, with a
+/// `NotAllowedError`, such as when the clipboard permission has been denied or the page doesn't
+/// have focus.
+pub async fn write_text(text: &str) -> Result<(), String> {
+    let navigator = window().navigator();
+    if !js_sys::Reflect::has(&navigator, &JsValue::from_str("clipboard")).unwrap_or(false) {
+        return Err("This browser doesn't allow copying from this page".to_string());
+    }
+    JsFuture::from(navigator.clipboard().write_text(text))
+        .await
+        .map(|_| ())
+        .map_err(|error| match error.dyn_ref::<web_sys::DomException>() {
+            Some(error) if error.name() == "NotAllowedError" => {
+                "The browser didn't allow copying. Check the page's clipboard permission."
+                    .to_string()
+            }
+            _ => "Couldn't copy to the clipboard".to_string(),
+        })
+}
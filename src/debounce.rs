@@ -0,0 +1,79 @@
+use leptos::prelude::*;
+use std::time::Duration;
+
+/// Calls `callback` with the latest value of `source` once it has stopped changing for `delay`.
+///
+/// Every change restarts the wait, so a burst of changes results in a single call. The value
+/// `source` starts with isn't passed to `callback`, only those it changes to. The returned signal
+/// is true whilst a call is waiting, for showing that something is about to happen.
+///
+/// Should the owner this is called under be cleaned up mid-wait, such as by navigating to
+/// another route, the waiting call is made there and then rather than dropped. Cleanups run
+/// before the owner's signals are disposed, so `callback` may still use any created alongside
+/// this.
+///
+/// Changes are only watched from an Effect, so nothing is ever called during SSR.
+pub fn debounce<T>(
+    source: Signal<T>,
+    delay: Duration,
+    callback: impl Fn(T) + Clone + Send + Sync + 'static,
+) -> Signal<bool>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    let pending = RwSignal::new(false);
+    let latest = StoredValue::new(None::<T>);
+    let timeout = StoredValue::new(None::<TimeoutHandle>);
+
+    let settle = move || {
+        if let Some(handle) = timeout.try_update_value(Option::take).flatten() {
+            handle.clear();
+        }
+        pending.set(false);
+        if let Some(value) = latest.try_update_value(Option::take).flatten() {
+            callback(value);
+        }
+    };
+
+    Effect::new({
+        let settle = settle.clone();
+        move |initialised: Option<()>| {
+            let value = source.get();
+            if initialised.is_some() {
+                latest.set_value(Some(value));
+                if let Some(handle) = timeout.get_value() {
+                    handle.clear();
+                }
+                pending.set(true);
+                timeout.set_value(set_timeout_with_handle(settle.clone(), delay).ok());
+            }
+        }
+    });
+
+    on_cleanup(move || {
+        if pending.get_untracked() {
+            settle();
+        }
+    });
+
+    pending.into()
+}
+
+/// A signal following `source`, but lagging behind it until it has stopped changing for `delay`,
+/// see [`debounce`]. For deriving something costly from a signal that changes in bursts, such
+/// as the text of an input, which itself stays bound to `source` and so as responsive as ever.
+///
+/// Until hydrated it simply reads `source`, as no Effect runs during SSR to catch it up. That
+/// way the server renders, and hydration finds, whatever `source` was set to whilst rendering,
+/// such as from the URL. Once hydrated it holds its own copy, updated only as each burst ends.
+pub fn debounced_signal<T>(source: Signal<T>, delay: Duration) -> Signal<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    let settled = RwSignal::new(None::<T>);
+    Effect::new(move |_| settled.set(Some(source.get_untracked())));
+    debounce(source, delay, move |value| {
+        settled.try_set(Some(value));
+    });
+    Signal::derive(move || settled.get().unwrap_or_else(|| source.get()))
+}
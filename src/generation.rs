@@ -0,0 +1,46 @@
+/// Numbers requests as they're made, so that when their answers arrive out of order, only the
+/// latest request's is applied and any earlier one arriving after it is discarded.
+///
+/// Neither a refetch nor an Action's dispatch cancels the request before it, so two in flight at
+/// once can finish in either order. Whichever finishes last would otherwise win, even if it was
+/// made first, and clobber the newer answer with an older one. Take a number with
+/// [`Generations::issue`] when making each request, carry it with the answer, and check it with
+/// [`Generations::is_latest`] before applying the answer.
+///
+/// Nothing here is reactive, so keep it in a `StoredValue`. Simulating two requests finishing
+/// out of order:
+///
+/// ```
+/// use my_app::generation::Generations;
+///
+/// let mut generations = Generations::default();
+/// let first = (generations.issue(), "older");
+/// let second = (generations.issue(), "newer");
+///
+/// let mut applied = None;
+/// // The second request answers first, and is applied as it's the latest.
+/// for (generation, answer) in [second, first] {
+///     if generations.is_latest(generation) {
+///         applied = Some(answer);
+///     }
+/// }
+/// // The first's answer, arriving later, is discarded.
+/// assert_eq!(applied, Some("newer"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Generations {
+    latest: u64,
+}
+
+impl Generations {
+    /// Numbers a new request, making it the latest.
+    pub fn issue(&mut self) -> u64 {
+        self.latest += 1;
+        self.latest
+    }
+
+    /// Whether `generation` was the latest request issued, and so its answer is to be applied.
+    pub fn is_latest(&self, generation: u64) -> bool {
+        generation == self.latest
+    }
+}
@@ -0,0 +1,62 @@
+/// An undo/redo stack of snapshots.
+///
+/// Nothing here is reactive. Wrap a `History` in a signal and take a snapshot of the Store
+/// **before** each mutation with [`History::record`]. Undoing hands back the snapshot to restore,
+/// having remembered the current state so it can be redone later.
+///
+/// The number of undo steps kept is capped at `depth` to bound memory, the oldest snapshot being
+/// discarded first.
+#[derive(Debug, Clone)]
+pub struct History<T> {
+    undo: Vec<T>,
+    redo: Vec<T>,
+    depth: usize,
+}
+
+impl<T> History<T> {
+    pub fn new(depth: usize) -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            depth,
+        }
+    }
+
+    /// Remembers the state prior to a mutation. Any undone steps can no longer be redone.
+    pub fn record(&mut self, snapshot: T) {
+        self.undo.push(snapshot);
+        if self.undo.len() > self.depth {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    /// Returns the state to restore, given the state it replaces.
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        let snapshot = self.undo.pop()?;
+        self.redo.push(current);
+        Some(snapshot)
+    }
+
+    /// Returns the state to restore, given the state it replaces.
+    pub fn redo(&mut self, current: T) -> Option<T> {
+        let snapshot = self.redo.pop()?;
+        self.undo.push(current);
+        Some(snapshot)
+    }
+
+    /// Forgets every step, such as once the state has been replaced by something that can't be
+    /// undone back past.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
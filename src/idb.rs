@@ -0,0 +1,122 @@
+use leptos::prelude::window;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbRequest, IdbTransaction, IdbTransactionMode};
+
+/// The IndexedDB database, and the object store within it, holding the persisted `Data`.
+const DB_NAME: &str = "leptos-stores-demo";
+const STORE_NAME: &str = "data";
+/// The one key in the object store, the whole of `Data` being kept as a single value.
+const KEY: &str = "data";
+
+/// Reads the JSON last written with [`save_items`], if any. Client only, as IndexedDB only
+/// exists in the browser.
+pub async fn load_items() -> Result<Option<String>, String> {
+    let db = open().await?;
+    let transaction = db
+        .transaction_with_str(STORE_NAME)
+        .map_err(|error| message(&error))?;
+    let request = transaction
+        .object_store(STORE_NAME)
+        .and_then(|store| store.get(&JsValue::from_str(KEY)))
+        .map_err(|error| message(&error))?;
+    Ok(settle(&request).await?.as_string())
+}
+
+/// Writes `json`, the enveloped `Data` as `storage` serializes it, replacing whatever was there.
+/// Client only, see [`load_items`].
+///
+/// IndexedDB reports running out of space with a `QuotaExceededError`, which may only surface
+/// once the transaction aborts, so the write isn't done until the transaction has completed.
+/// Either way the error is turned into a message fit for a toast.
+pub async fn save_items(json: &str) -> Result<(), String> {
+    let db = open().await?;
+    let transaction = db
+        .transaction_with_str_and_mode(STORE_NAME, IdbTransactionMode::Readwrite)
+        .map_err(|error| message(&error))?;
+    let done = completion(&transaction);
+    transaction
+        .object_store(STORE_NAME)
+        .and_then(|store| store.put_with_key(&JsValue::from_str(json), &JsValue::from_str(KEY)))
+        .map_err(|error| message(&error))?;
+    JsFuture::from(done).await.map(|_| ()).map_err(|_| {
+        transaction
+            .error()
+            .map_or_else(unknown, |error| describe(&error))
+    })
+}
+
+/// Opens the database, creating its object store the first time.
+async fn open() -> Result<IdbDatabase, String> {
+    let factory = window()
+        .indexed_db()
+        .ok()
+        .flatten()
+        .ok_or_else(|| "IndexedDB isn't available in this browser".to_string())?;
+    let request = factory
+        .open_with_u32(DB_NAME, 1)
+        .map_err(|error| message(&error))?;
+    // Only called when the database is first created, or its version is bumped, which is when
+    // object stores may be created. The closure must live until the open has settled.
+    let upgrade = {
+        let request = request.clone();
+        Closure::<dyn FnMut(web_sys::Event)>::new(move |_| {
+            if let Ok(db) = request.result() {
+                let _ = db
+                    .unchecked_into::<IdbDatabase>()
+                    .create_object_store(STORE_NAME);
+            }
+        })
+    };
+    request.set_onupgradeneeded(Some(upgrade.as_ref().unchecked_ref()));
+    let db = settle(&request).await;
+    request.set_onupgradeneeded(None);
+    Ok(db?.unchecked_into())
+}
+
+/// Waits for `request` to succeed, answering with its result, or fail.
+async fn settle(request: &IdbRequest) -> Result<JsValue, String> {
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        request.set_onsuccess(Some(&resolve));
+        request.set_onerror(Some(&reject));
+    });
+    let settled = JsFuture::from(promise).await;
+    request.set_onsuccess(None);
+    request.set_onerror(None);
+    if settled.is_err() {
+        return Err(request
+            .error()
+            .ok()
+            .flatten()
+            .map_or_else(unknown, |error| describe(&error)));
+    }
+    request.result().map_err(|error| message(&error))
+}
+
+/// A promise resolving once `transaction` completes, or rejecting should it fail or abort.
+fn completion(transaction: &IdbTransaction) -> js_sys::Promise {
+    js_sys::Promise::new(&mut |resolve, reject| {
+        transaction.set_oncomplete(Some(&resolve));
+        transaction.set_onerror(Some(&reject));
+        transaction.set_onabort(Some(&reject));
+    })
+}
+
+fn describe(error: &web_sys::DomException) -> String {
+    if error.name() == "QuotaExceededError" {
+        "The browser has run out of space to save the list. Export it to keep a copy.".to_string()
+    } else {
+        format!("IndexedDB failed. {}", error.message())
+    }
+}
+
+fn message(error: &JsValue) -> String {
+    match error.dyn_ref::<web_sys::DomException>() {
+        Some(error) => describe(error),
+        None => unknown(),
+    }
+}
+
+fn unknown() -> String {
+    "Couldn't reach IndexedDB".to_string()
+}
@@ -0,0 +1,65 @@
+use leptos::prelude::Owner;
+
+/// A new random ID for an item or sub-item, from a version 4 UUID.
+///
+/// Call this from event handlers and server fns only, **never** while rendering. The server
+/// renders the page, and the browser then renders it again to hydrate it, each calling this
+/// afresh. An ID generated while rendering would differ between the two, so the keyed For would
+/// see keys the server's markup doesn't have, and hydration would go wrong in ways that are
+/// hard to trace back here.
+///
+/// Instead, the initial items come with their IDs from the server, in `get_items`, and are sent
+/// to the browser along with the page for hydration to reuse. New items are only created by
+/// handlers, which never run during hydration. That's checked here, in debug builds, with a
+/// warning should this be called whilst hydrating. Where matching IDs are needed on both sides,
+/// use [`SeededIds`] instead.
+pub fn new_id() -> u128 {
+    if cfg!(debug_assertions)
+        && Owner::current_shared_context().is_some_and(|context| context.during_hydration())
+    {
+        leptos::logging::warn!(
+            "An ID was generated during hydration, which won't match the server's. Generate IDs \
+             in handlers, or use `SeededIds`."
+        );
+    }
+    uuid::Uuid::new_v4().as_u128()
+}
+
+/// Generates the same sequence of IDs wherever it's given the same seed, so the server and the
+/// browser can each generate them whilst rendering and agree.
+///
+/// The seed must itself be something both sides know, such as a constant or a route param, and
+/// IDs must be taken in the same order on both. They're spread across all 128 bits, using
+/// SplitMix64, so they're as unlikely to collide with random ones as random ones are with each
+/// other. They're not random, though, so don't use them anywhere an ID mustn't be guessable.
+///
+/// ```
+/// use my_app::ids::SeededIds;
+///
+/// let mut server = SeededIds::new(42);
+/// let mut client = SeededIds::new(42);
+/// assert_eq!(server.next_id(), client.next_id());
+/// assert_ne!(server.next_id(), SeededIds::new(7).next_id());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SeededIds {
+    state: u64,
+}
+
+impl SeededIds {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_id(&mut self) -> u128 {
+        (u128::from(self.next_u64()) << 64) | u128::from(self.next_u64())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
@@ -1,4 +1,14 @@
 pub mod app;
+pub mod clipboard;
+pub mod debounce;
+pub mod generation;
+pub mod history;
+pub mod idb;
+pub mod ids;
+pub mod markdown;
+pub mod share;
+pub mod storage;
+pub mod time;
 
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
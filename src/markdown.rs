@@ -0,0 +1,202 @@
+use crate::app::split_matches;
+
+/// Renders an item's value from the little Markdown it may hold, `**bold**`, `*italics*` or
+/// `_italics_`, and `[links](https://example.com)`, to HTML for a row's `inner_html`, with
+/// whatever matches `highlight` marked as the plain rows are, see `split_matches`.
+///
+/// # Trust
+///
+/// Values are whatever anyone typed, pasted, imported or opened from a share link, so none of
+/// it can be trusted with `inner_html`. Rather than rendering Markdown however it likes and
+/// then cleaning up the HTML afterwards, this never lets anything from `source` through as
+/// HTML in the first place. Every run of text is escaped, `<`, `>`, `&` and both quotes
+/// included, and the only tags ever written are the `strong`, `em`, `a` and `mark` written
+/// here, so HTML within a value, a `<script>` or an `onerror`, shows up as the text it is.
+///
+/// The one thing taken from `source` into an attribute is a link's URL, which is escaped too,
+/// and only linked at all when it's `http:`, `https:` or `mailto:`, or a path on this site,
+/// but not one a browser would read as another site's, such as `//elsewhere` or `/\elsewhere`.
+/// Anything else, `javascript:` and `data:` URLs especially, leaves the link's text unlinked.
+/// Links open in a new tab, with `noopener` so the page opened can't reach back into this one.
+///
+/// Anything that isn't one of the above is left as written, an unclosed `**` included, and a
+/// backslash keeps the character after it from being read as Markdown. Markup splits the text
+/// it's highlighted within, so a match straddling it isn't marked.
+///
+/// ```
+/// use my_app::markdown::to_html;
+///
+/// assert_eq!(
+///     to_html("**Milk**, *oat* [please](https://example.com)", ""),
+///     "<strong>Milk</strong>, <em>oat</em> <a href=\"https://example.com\" \
+///      target=\"_blank\" rel=\"noopener noreferrer nofollow\" \
+///      class=\"text-sky-600 underline\">please</a>",
+/// );
+/// assert_eq!(
+///     to_html("[sorted](/sorted)", ""),
+///     "<a href=\"/sorted\" target=\"_blank\" rel=\"noopener noreferrer nofollow\" \
+///      class=\"text-sky-600 underline\">sorted</a>",
+/// );
+/// assert_eq!(to_html("snake_case and 2 * 3", ""), "snake_case and 2 * 3");
+/// assert_eq!(to_html(r"\*not italics\*", ""), "*not italics*");
+/// assert_eq!(
+///     to_html("oat *milk*", "MILK"),
+///     "oat <em><mark class=\"bg-yellow-200 rounded-sm\">milk</mark></em>",
+/// );
+/// ```
+///
+/// Whilst HTML, and URLs that aren't safe to follow, come out as harmless text:
+///
+/// ```
+/// use my_app::markdown::to_html;
+///
+/// assert_eq!(
+///     to_html("<img src=x onerror=alert(1)>", ""),
+///     "&lt;img src=x onerror=alert(1)&gt;",
+/// );
+/// assert_eq!(to_html("[click](JavaScript:alert`1`)", ""), "click");
+/// assert_eq!(to_html("[click](data:text/html,<script>)", ""), "click");
+/// assert_eq!(to_html("[home](//evil.example)", ""), "home");
+/// assert_eq!(to_html(r"[home](/\evil.example)", ""), "home");
+/// assert_eq!(to_html("[home](/\t/evil.example)", ""), "home");
+/// assert_eq!(
+///     to_html("[x](https://example.com/\"onmouseover=\"alert(1))", ""),
+///     "<a href=\"https://example.com/&quot;onmouseover=&quot;alert(1\" target=\"_blank\" \
+///      rel=\"noopener noreferrer nofollow\" class=\"text-sky-600 underline\">x</a>)",
+/// );
+/// ```
+pub fn to_html(source: &str, highlight: &str) -> String {
+    let mut html = String::new();
+    render(source, highlight, false, &mut html);
+    html
+}
+
+/// Renders `source` into `html`, see `to_html`. Links can't be nested, so within one's text
+/// `in_link` leaves brackets be.
+fn render(source: &str, highlight: &str, in_link: bool, html: &mut String) {
+    let mut text = String::new();
+    let mut rest = source;
+    while let Some(c) = rest.chars().next() {
+        let after = &rest[c.len_utf8()..];
+        if c == '\\' {
+            if let Some(escaped) = after.chars().next() {
+                text.push(escaped);
+                rest = &after[escaped.len_utf8()..];
+                continue;
+            }
+        }
+        if let Some((inner, next)) = enclosed(rest, "**") {
+            push_text(&mut text, highlight, html);
+            html.push_str("<strong>");
+            render(inner, highlight, in_link, html);
+            html.push_str("</strong>");
+            rest = next;
+            continue;
+        }
+        // An underscore within a word, such as in snake_case, is just an underscore.
+        let word_before = text.chars().next_back().is_some_and(char::is_alphanumeric);
+        if let Some((inner, next)) =
+            enclosed(rest, "*").or_else(|| enclosed(rest, "_").filter(|_| !word_before))
+        {
+            push_text(&mut text, highlight, html);
+            html.push_str("<em>");
+            render(inner, highlight, in_link, html);
+            html.push_str("</em>");
+            rest = next;
+            continue;
+        }
+        if let Some((label, url, next)) = link(rest).filter(|_| !in_link) {
+            push_text(&mut text, highlight, html);
+            match safe_url(url) {
+                Some(url) => {
+                    html.push_str("<a href=\"");
+                    html.push_str(&escape(url));
+                    html.push_str(
+                        "\" target=\"_blank\" rel=\"noopener noreferrer nofollow\" \
+                         class=\"text-sky-600 underline\">",
+                    );
+                    render(label, highlight, true, html);
+                    html.push_str("</a>");
+                }
+                None => render(label, highlight, true, html),
+            }
+            rest = next;
+            continue;
+        }
+        text.push(c);
+        rest = after;
+    }
+    push_text(&mut text, highlight, html);
+}
+
+/// Splits `source`, if it opens with `marker`, into what's between that and the next
+/// `marker`, and what follows. Nothing is enclosed if there's nothing between them, or if what
+/// is between starts or ends with a space, so `2 * 3 * 4` is left be.
+fn enclosed<'a>(source: &'a str, marker: &str) -> Option<(&'a str, &'a str)> {
+    let inner = source.strip_prefix(marker)?;
+    let end = inner.find(marker)?;
+    let enclosed = &inner[..end];
+    let spaced =
+        enclosed.starts_with(char::is_whitespace) || enclosed.ends_with(char::is_whitespace);
+    (!enclosed.is_empty() && !spaced).then(|| (enclosed, &inner[end + marker.len()..]))
+}
+
+/// Splits `source`, if it opens with a `[label](url)`, into its label, its URL and what
+/// follows. The URL runs to the first `)`, so one can't hold a `)` of its own.
+fn link(source: &str) -> Option<(&str, &str, &str)> {
+    let inner = source.strip_prefix('[')?;
+    let (label, rest) = inner.split_once("](")?;
+    let (url, rest) = rest.split_once(')')?;
+    (!label.is_empty() && !label.contains('[')).then_some((label, url.trim(), rest))
+}
+
+/// `url`, should it be safe to link to, see `to_html`.
+fn safe_url(url: &str) -> Option<&str> {
+    // Browsers drop tabs and line breaks from URLs, so `/\t/elsewhere` would be `//elsewhere`
+    // once followed. No URL worth linking needs them, nor spaces or control characters.
+    if url.contains(|c: char| c.is_whitespace() || c.is_control()) {
+        return None;
+    }
+    let lower = url.to_lowercase();
+    let external = ["http://", "https://", "mailto:"]
+        .iter()
+        .any(|scheme| lower.starts_with(scheme));
+    // A path on this site, but not `//elsewhere`, which would be another site altogether, nor
+    // `/\elsewhere`, which browsers read the same way.
+    let local = url.starts_with('/') && !url[1..].starts_with(['/', '\\']);
+    (external || local).then_some(url)
+}
+
+/// Escapes the pending run of `text` into `html`, marking what matches `highlight`, and
+/// empties it.
+fn push_text(text: &mut String, highlight: &str, html: &mut String) {
+    if text.is_empty() {
+        return;
+    }
+    for (part, matched) in split_matches(text, highlight) {
+        if matched {
+            html.push_str("<mark class=\"bg-yellow-200 rounded-sm\">");
+            html.push_str(&escape(&part));
+            html.push_str("</mark>");
+        } else {
+            html.push_str(&escape(&part));
+        }
+    }
+    text.clear();
+}
+
+/// Escapes `text` for HTML, whether between tags or within a quoted attribute.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,194 @@
+use crate::{app::Data, storage};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use leptos::prelude::window;
+use miniz_oxide::{deflate::compress_to_vec, inflate::decompress_to_vec_with_limit};
+
+/// The URL fragment's prefix, ahead of the encoded state, as in `#state=...`.
+pub const FRAGMENT_PREFIX: &str = "#state=";
+
+/// The longest encoded state put in a link. Browsers cope with far longer URLs, but plenty of
+/// the places a link gets pasted, chat apps and the like, truncate them well before then.
+pub const SHARE_LIMIT: usize = 2_000;
+
+/// The most a shared link is allowed to decompress to, lest a few bytes of link unpack into
+/// gigabytes.
+const INFLATE_LIMIT: usize = 16 * 1024 * 1024;
+
+/// The ten byte gzip header: the magic number, deflate, no flags, no modification time, no extra
+/// flags and an unknown OS.
+const GZIP_HEADER: [u8; 10] = [0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff];
+
+/// Encodes `Data` to go in a URL, as gzipped JSON in URL-safe base64 without padding.
+///
+/// The JSON is the same envelope as an export, see `storage::export`, so a link outlives changes
+/// to `Data` just as a file does.
+pub fn encode_state(data: &Data) -> String {
+    let json = storage::to_envelope(data, false).unwrap_or_default();
+    URL_SAFE_NO_PAD.encode(gzip(json.as_bytes()))
+}
+
+/// Decodes `Data` from [`encode_state`]'s output, refusing anything an import would refuse, see
+/// `storage::parse_import`.
+///
+/// A list round-trips exactly, IDs too large for a `u64` and sub-items included:
+///
+/// ```
+/// use leptos::prelude::*;
+/// use my_app::{
+///     app::{Data, DataStoreFields, Item, SubItem},
+///     share::{decode_state, encode_state},
+/// };
+/// use reactive_stores::Store;
+///
+/// let owner = Owner::new();
+/// owner.set();
+/// let store = Store::new(Data::default());
+/// store.items().set(vec![Item {
+///     id: u128::MAX,
+///     value: "Milk".to_string(),
+///     tags: vec!["dairy".to_string()],
+///     children: vec![SubItem { id: u128::MAX - 1, value: "Oat".to_string() }],
+///     ..Item::default()
+/// }]);
+///
+/// let encoded = encode_state(&store.get_untracked());
+/// let decoded = Store::new(decode_state(&encoded).unwrap());
+/// assert_eq!(decoded.items().get_untracked(), store.items().get_untracked());
+/// ```
+///
+/// Whereas a link that's been cut short or mangled in transit is refused, the CRC catching
+/// whatever still inflates:
+///
+/// ```
+/// use my_app::{app::Data, share::{decode_state, encode_state}};
+///
+/// let encoded = encode_state(&Data::default());
+/// let middle = encoded.len() / 2;
+/// let flipped = if &encoded[middle..=middle] == "A" { "B" } else { "A" };
+/// let corrupt = format!("{}{flipped}{}", &encoded[..middle], &encoded[middle + 1..]);
+///
+/// assert!(decode_state(&corrupt).is_err());
+/// assert!(decode_state(&encoded[..middle]).is_err());
+/// assert!(decode_state("not a link!").is_err());
+/// ```
+pub fn decode_state(encoded: &str) -> Result<Data, String> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(encoded.trim())
+        .map_err(|_| "The link is damaged".to_string())?;
+    let json = gunzip(&bytes)?;
+    let json = String::from_utf8(json).map_err(|_| "The link is damaged".to_string())?;
+    storage::parse_import(&json)
+}
+
+/// Builds a link to the current page carrying `Data` in its fragment, or `None` should it be
+/// longer than [`SHARE_LIMIT`], in which case offer the file export instead. Client only.
+///
+/// The fragment is never sent to the server, so neither is the list.
+pub fn share_link(data: &Data) -> Option<String> {
+    let encoded = encode_state(data);
+    if encoded.len() > SHARE_LIMIT {
+        return None;
+    }
+    let location = window().location();
+    let origin = location.origin().ok()?;
+    let path = location.pathname().ok()?;
+    Some(format!("{origin}{path}{FRAGMENT_PREFIX}{encoded}"))
+}
+
+/// Takes the state out of the current URL's fragment, if it carries any, replacing the URL with
+/// one without it so reloading doesn't open the same list over any changes since. Client only.
+pub fn take_from_location() -> Option<Result<Data, String>> {
+    let location = window().location();
+    let hash = location.hash().ok()?;
+    let encoded = hash.strip_prefix(FRAGMENT_PREFIX)?;
+    let data = decode_state(encoded);
+    if let (Ok(history), Ok(path), Ok(search)) =
+        (window().history(), location.pathname(), location.search())
+    {
+        let _ = history.replace_state_with_url(
+            &wasm_bindgen::JsValue::NULL,
+            "",
+            Some(&format!("{path}{search}")),
+        );
+    }
+    Some(data)
+}
+
+/// Wraps `bytes`, deflated, in the gzip format, see RFC 1952.
+fn gzip(bytes: &[u8]) -> Vec<u8> {
+    let mut gzip = GZIP_HEADER.to_vec();
+    gzip.extend(compress_to_vec(bytes, 9));
+    gzip.extend(crc32(bytes).to_le_bytes());
+    gzip.extend((bytes.len() as u32).to_le_bytes());
+    gzip
+}
+
+/// Unwraps what [`gzip`] wrapped, checking its CRC. Only the header `gzip` writes is understood,
+/// as that's the only one a link should carry.
+fn gunzip(gzip: &[u8]) -> Result<Vec<u8>, String> {
+    let damaged = || "The link is damaged".to_string();
+    if gzip.len() < GZIP_HEADER.len() + 8 || gzip[..4] != GZIP_HEADER[..4] {
+        return Err(damaged());
+    }
+    let (deflated, trailer) =
+        gzip[GZIP_HEADER.len()..].split_at(gzip.len() - GZIP_HEADER.len() - 8);
+    let bytes = decompress_to_vec_with_limit(deflated, INFLATE_LIMIT).map_err(|_| damaged())?;
+    let crc = u32::from_le_bytes(trailer[..4].try_into().map_err(|_| damaged())?);
+    if crc32(&bytes) != crc {
+        return Err(damaged());
+    }
+    Ok(bytes)
+}
+
+/// The CRC-32 gzip checks its contents with, computed bit by bit. The lists are small enough
+/// that a lookup table wouldn't be worth its space in the binary.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0_u32;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The standard check value, so the CRC isn't merely self-consistent but the one any gzip
+    /// reader expects.
+    #[test]
+    fn crc32_matches_the_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn gzip_round_trips_and_writes_the_header_and_trailer() {
+        let bytes = b"a list, a list, a list";
+        let gzipped = gzip(bytes);
+        assert_eq!(gzipped[..GZIP_HEADER.len()], GZIP_HEADER);
+        let trailer = &gzipped[gzipped.len() - 8..];
+        assert_eq!(trailer[..4], crc32(bytes).to_le_bytes());
+        assert_eq!(trailer[4..], (bytes.len() as u32).to_le_bytes());
+        assert_eq!(gunzip(&gzipped).unwrap(), bytes);
+    }
+
+    #[test]
+    fn gunzip_refuses_a_bad_header_or_crc() {
+        let mut gzipped = gzip(b"items");
+        gzipped[0] = 0;
+        assert!(gunzip(&gzipped).is_err());
+
+        let mut gzipped = gzip(b"items");
+        let crc = gzipped.len() - 8;
+        gzipped[crc] ^= 1;
+        assert!(gunzip(&gzipped).is_err());
+    }
+}
@@ -0,0 +1,429 @@
+use crate::{
+    app::{Data, Item, Mutation, Preferences},
+    idb,
+    time::{format_date, now_ms},
+};
+use leptos::prelude::{document, window};
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// The localStorage key under which the demo's `Data` is persisted.
+pub const STORAGE_KEY: &str = "leptos-stores-demo";
+
+/// The version of the shape `Data` is serialized in, written alongside it in every [`Envelope`].
+///
+/// Bump it whenever that shape changes in a way `#[serde(default)]` can't paper over, such as a
+/// field being renamed or changing type, and teach [`migrate`] how to upgrade the version
+/// before.
+pub const DATA_VERSION: u32 = 1;
+
+/// `Data` as persisted to localStorage and exported, wrapped with the version it was written
+/// at, so that a later build can tell how to read it.
+#[derive(Serialize, Deserialize)]
+struct Envelope<T> {
+    version: u32,
+    data: T,
+}
+
+/// Serializes `Data`, wrapped in an [`Envelope`] at the current [`DATA_VERSION`].
+pub fn to_envelope(data: &Data, pretty: bool) -> serde_json::Result<String> {
+    let envelope = Envelope {
+        version: DATA_VERSION,
+        data,
+    };
+    if pretty {
+        serde_json::to_string_pretty(&envelope)
+    } else {
+        serde_json::to_string(&envelope)
+    }
+}
+
+/// Deserializes `Data` from any version of its envelope, or from no envelope at all, as was
+/// persisted and exported before there was one. See [`migrate`].
+///
+/// The data is kept as raw JSON until its version is known, rather than parsed into a
+/// `serde_json::Value`, which would round item IDs too large for a `u64` to the nearest `f64`.
+///
+/// A bare `Data` from before the envelope, its item missing every field added since, comes
+/// back with those fields defaulted, and its IDs, sub-items' included, exactly as they were:
+///
+/// ```
+/// use leptos::prelude::*;
+/// use my_app::{
+///     app::{Data, DataStoreFields, Item, SubItem},
+///     storage::{from_envelope, to_envelope},
+/// };
+/// use reactive_stores::Store;
+///
+/// let owner = Owner::new();
+/// owner.set();
+/// let old = r#"{"items": [{
+///     "id": 340282366920938463463374607431768211455,
+///     "value": "Milk",
+///     "children": [{"id": 226854911280625642308916404954512140970, "value": "Oat"}]
+/// }]}"#;
+/// let items = Store::new(from_envelope(old).unwrap()).items().get_untracked();
+/// assert_eq!(
+///     items,
+///     [Item {
+///         id: u128::MAX,
+///         value: "Milk".to_string(),
+///         children: vec![SubItem {
+///             id: 226854911280625642308916404954512140970,
+///             value: "Oat".to_string(),
+///         }],
+///         ..Item::default()
+///     }],
+/// );
+///
+/// // And what's written now reads back the same.
+/// let data = Store::new(Data::default());
+/// data.items().set(items.clone());
+/// let json = to_envelope(&data.get_untracked(), false).unwrap();
+/// assert_eq!(Store::new(from_envelope(&json).unwrap()).items().get_untracked(), items);
+/// ```
+pub fn from_envelope(json: &str) -> Result<Data, String> {
+    match serde_json::from_str::<Envelope<&RawValue>>(json) {
+        Ok(Envelope { version, data }) => migrate(version, data.get()),
+        Err(_) => migrate(0, json),
+    }
+}
+
+/// Upgrades `Data` serialized at `version` to the current shape.
+///
+/// - Version 0 is anything written before the envelope existed, a bare `Data`. Its items may
+///   predate any of the fields added since the first, such as `done` or `tags`, and the trash
+///   may be missing altogether. Every such field is `#[serde(default)]`, so each is filled with
+///   its default as it's deserialized.
+///
+/// Version 1 is the same shape, only enveloped. A version newer than [`DATA_VERSION`] was
+/// written by a newer build, and rather than guessing at it, or failing somewhere deep within
+/// `Deserialize`, an error says as much.
+pub fn migrate(version: u32, json: &str) -> Result<Data, String> {
+    match version {
+        0 | 1 => serde_json::from_str(json).map_err(|error| format!("Not a valid export: {error}")),
+        _ => Err(format!(
+            "Saved by a newer version of the demo, version {version}, which this version \
+             ({DATA_VERSION}) can't read"
+        )),
+    }
+}
+
+/// The most JSON, in bytes, persisted to localStorage. Any more and `Data` is persisted to
+/// IndexedDB instead, see [`save`].
+///
+/// Browsers allow localStorage only a few megabytes, counted in UTF-16, and every read and write
+/// of it blocks the page, so a large list is better off elsewhere.
+pub const IDB_THRESHOLD: usize = 1024 * 1024;
+
+/// Reads any `Data` previously persisted by [`save`], migrating it from an older version if need
+/// be. Anything unreadable, or written by a newer version, is treated as nothing at all.
+///
+/// localStorage is checked first, as `save` clears it whenever it uses IndexedDB instead.
+///
+/// localStorage and IndexedDB only exist in the browser, so this must only be called on the
+/// client, such as from within an `Effect`, which never runs during SSR.
+pub async fn load() -> Option<Data> {
+    let stored = window()
+        .local_storage()
+        .ok()
+        .flatten()
+        .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten());
+    let json = match stored {
+        Some(json) => json,
+        None => idb::load_items().await.ok()??,
+    };
+    from_envelope(&json).ok()
+}
+
+/// Persists `Data`, within an envelope, to localStorage or, once it's larger than
+/// [`IDB_THRESHOLD`], to IndexedDB. Answers with roughly how many bytes it took up. Client only,
+/// see [`load`].
+///
+/// localStorage may well be full before the threshold is reached, as its quota is shared with
+/// anything else on the same origin, in which case the write throws a `QuotaExceededError`. A
+/// failed write leaves the previous value be, so this falls back to IndexedDB, only failing
+/// should that fail too.
+///
+/// Moving to IndexedDB only clears localStorage once the write there has succeeded, so a failed
+/// write leaves the last one that succeeded to load. Whichever copy isn't in use is left to go
+/// stale, as `load` prefers localStorage and `save` only leaves that set when it's the newer.
+/// Either way, nothing here touches the Store.
+pub async fn save(data: Data) -> Result<usize, String> {
+    let json = to_envelope(&data, false).map_err(|error| error.to_string())?;
+    let storage = window().local_storage().ok().flatten();
+    if json.len() <= IDB_THRESHOLD {
+        if let Some(storage) = &storage {
+            match storage.set_item(STORAGE_KEY, &json) {
+                // localStorage holds strings as UTF-16, two bytes to a code unit.
+                Ok(()) => return Ok(json.encode_utf16().count() * 2),
+                Err(error) if !quota_exceeded(&error) => {
+                    return Err("Couldn't save the list to localStorage".to_string());
+                }
+                Err(_) => {}
+            }
+        }
+    }
+    idb::save_items(&json).await?;
+    if let Some(storage) = storage {
+        let _ = storage.remove_item(STORAGE_KEY);
+    }
+    Ok(json.len())
+}
+
+/// Whether a storage write failed for want of space. Firefox once had its own name for it.
+fn quota_exceeded(error: &JsValue) -> bool {
+    error
+        .dyn_ref::<web_sys::DomException>()
+        .is_some_and(|error| {
+            matches!(
+                error.name().as_str(),
+                "QuotaExceededError" | "NS_ERROR_DOM_QUOTA_REACHED"
+            )
+        })
+}
+
+/// Formats a number of bytes for people, such as "12.3 KB".
+pub fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        0..1_000 => format!("{bytes} B"),
+        1_000..1_000_000 => format!("{:.1} KB", bytes as f64 / 1_000.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_000_000.0),
+    }
+}
+
+/// The localStorage key under which the last items answered by `get_items` are cached.
+pub const CACHE_KEY: &str = "leptos-stores-demo-cache";
+
+/// Bump whenever `Item` changes shape, so a cache written by an older build is ignored rather
+/// than misread or, lacking some field, discarded with an unhelpful error.
+pub const CACHE_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct Cache {
+    version: u32,
+    items: Vec<Item>,
+}
+
+/// Reads the cached items, provided they were cached at the current `CACHE_VERSION`. Client
+/// only, see [`load`].
+pub fn load_cache() -> Option<Vec<Item>> {
+    let storage = window().local_storage().ok()??;
+    let json = storage.get_item(CACHE_KEY).ok()??;
+    let cache = serde_json::from_str::<Cache>(&json).ok()?;
+    (cache.version == CACHE_VERSION).then_some(cache.items)
+}
+
+/// Caches the items answered by `get_items`, see [`load_cache`]. Client only, see [`load`].
+pub fn save_cache(items: &[Item]) {
+    let Ok(Some(storage)) = window().local_storage() else {
+        return;
+    };
+    let cache = Cache {
+        version: CACHE_VERSION,
+        items: items.to_vec(),
+    };
+    if let Ok(json) = serde_json::to_string(&cache) {
+        let _ = storage.set_item(CACHE_KEY, &json);
+    }
+}
+
+/// The localStorage key under which mutations still waiting to reach the server are persisted,
+/// kept apart from `Data` so the queue survives a reload whether or not the list is restored.
+pub const PENDING_KEY: &str = "leptos-stores-demo-pending";
+
+/// Reads the queue of pending mutations, see `Mutation`. Client only, see [`load`].
+pub fn load_pending() -> Option<Vec<Mutation>> {
+    let storage = window().local_storage().ok()??;
+    let json = storage.get_item(PENDING_KEY).ok()??;
+    serde_json::from_str(&json).ok()
+}
+
+/// Persists the queue of pending mutations, removing the key altogether once it's empty. Client
+/// only, see [`load`].
+pub fn save_pending(pending: &[Mutation]) {
+    let Ok(Some(storage)) = window().local_storage() else {
+        return;
+    };
+    if pending.is_empty() {
+        let _ = storage.remove_item(PENDING_KEY);
+    } else if let Ok(json) = serde_json::to_string(pending) {
+        let _ = storage.set_item(PENDING_KEY, &json);
+    }
+}
+
+/// The localStorage key under which `Preferences` are persisted, apart from `Data` as they're
+/// not part of the list.
+pub const PREFERENCES_KEY: &str = "leptos-stores-demo-preferences";
+
+/// Reads the saved `Preferences`, if any. Client only, see [`load`].
+pub fn load_preferences() -> Option<Preferences> {
+    let storage = window().local_storage().ok()??;
+    let json = storage.get_item(PREFERENCES_KEY).ok()??;
+    serde_json::from_str(&json).ok()
+}
+
+/// Persists `Preferences`, see [`load_preferences`]. Client only, see [`load`].
+pub fn save_preferences(preferences: &Preferences) {
+    let Ok(Some(storage)) = window().local_storage() else {
+        return;
+    };
+    if let Ok(json) = serde_json::to_string(preferences) {
+        let _ = storage.set_item(PREFERENCES_KEY, &json);
+    }
+}
+
+/// Whether the browser believes it's online. Client only, see [`load`].
+pub fn online() -> bool {
+    window().navigator().on_line()
+}
+
+/// Downloads `Data` as a pretty-printed JSON file named `items-<timestamp>.json`, for backing up
+/// before anything destructive. Client only, see [`download`].
+pub fn export(data: &Data) {
+    if let Ok(json) = to_envelope(data, true) {
+        download(
+            &json,
+            "application/json",
+            &format!("items-{}.json", now_ms()),
+        );
+    }
+}
+
+/// Downloads the items as a CSV file named `items-<timestamp>.csv`, see [`items_to_csv`].
+/// Client only, see [`download`].
+pub fn export_csv(items: &[Item]) {
+    download(
+        &items_to_csv(items),
+        "text/csv",
+        &format!("items-{}.csv", now_ms()),
+    );
+}
+
+/// Renders items as CSV, with a header row naming `Item`'s fields followed by one row per item.
+///
+/// Every field gets a column, bar `children`, as a list of sub-items doesn't flatten into a
+/// cell, so export as JSON to keep those. An item's tags are joined with semicolons into a
+/// single column, its status and colour are given by their labels, and its due date as
+/// `YYYY-MM-DD`. Whatever's missing, such as an item with no description, is left empty.
+///
+/// Values containing a comma, quote or line break are quoted, with any quotes within doubled.
+///
+/// ```
+/// use my_app::{app::{Item, Status}, storage::items_to_csv};
+///
+/// let items = [
+///     Item { id: 1, value: "Milk, oat".to_string(), ..Item::default() },
+///     Item {
+///         id: 2,
+///         value: "The \"good\" bread".to_string(),
+///         description: Some("Two loaves\nsliced".to_string()),
+///         status: Status::Done,
+///         tags: vec!["bakery".to_string(), "weekly".to_string()],
+///         due: Some(0),
+///         ..Item::default()
+///     },
+/// ];
+/// assert_eq!(
+///     items_to_csv(&items),
+///     "id,value,quantity,done,created_at,tags,description,status,favorite,color,due\r\n\
+///      1,\"Milk, oat\",0,false,0,,,Todo,false,,\r\n\
+///      2,\"The \"\"good\"\" bread\",0,false,0,bakery;weekly,\"Two loaves\nsliced\",Done,false,,\
+///      1970-01-01\r\n",
+/// );
+/// ```
+pub fn items_to_csv(items: &[Item]) -> String {
+    let mut csv = String::from(
+        "id,value,quantity,done,created_at,tags,description,status,favorite,color,due\r\n",
+    );
+    for item in items {
+        let row = [
+            item.id.to_string(),
+            item.value.clone(),
+            item.quantity.to_string(),
+            item.done.to_string(),
+            item.created_at.to_string(),
+            item.tags.join(";"),
+            item.description.clone().unwrap_or_default(),
+            item.status.label().to_string(),
+            item.favorite.to_string(),
+            item.color
+                .map(|color| color.label())
+                .unwrap_or_default()
+                .to_string(),
+            item.due.map(format_date).unwrap_or_default(),
+        ];
+        let row = row.iter().map(|field| csv_field(field)).collect::<Vec<_>>();
+        csv.push_str(&row.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Downloads `contents` as a file named `filename`.
+///
+/// The contents are wrapped in a Blob, given an object URL, and handed to a temporary link which
+/// is clicked on the user's behalf. Client only, see [`load`]. Calling it from a click handler is
+/// enough, as event handlers never run during SSR.
+fn download(contents: &str, mime: &str, filename: &str) {
+    let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime);
+    let Ok(blob) = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options) else {
+        return;
+    };
+    let Ok(url) = web_sys::Url::create_object_url_with_blob(&blob) else {
+        return;
+    };
+    if let Some(link) = document()
+        .create_element("a")
+        .ok()
+        .and_then(|element| element.dyn_into::<web_sys::HtmlAnchorElement>().ok())
+    {
+        link.set_href(&url);
+        link.set_download(filename);
+        link.click();
+    }
+    let _ = web_sys::Url::revoke_object_url(&url);
+}
+
+/// Reads a file chosen through an `<input type="file">` as text.
+///
+/// FileReader reports back through its `onload` and `onerror` callbacks, so these are wired
+/// straight to a Promise's resolve and reject functions, which is then awaited. Client only, see
+/// [`load`].
+pub async fn read_file(file: web_sys::File) -> Result<String, String> {
+    let reader = web_sys::FileReader::new().map_err(|_| "Couldn't read the file".to_string())?;
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        reader.set_onload(Some(&resolve));
+        reader.set_onerror(Some(&reject));
+    });
+    reader
+        .read_as_text(&file)
+        .map_err(|_| "Couldn't read the file".to_string())?;
+    wasm_bindgen_futures::JsFuture::from(promise)
+        .await
+        .map_err(|_| "Couldn't read the file".to_string())?;
+    reader
+        .result()
+        .ok()
+        .and_then(|result| result.as_string())
+        .ok_or_else(|| "Couldn't read the file".to_string())
+}
+
+/// Parses exported JSON back into `Data`, migrating it from whichever version exported it, see
+/// [`from_envelope`], and refusing anything the Store shouldn't hold, see `Data::validate`.
+pub fn parse_import(json: &str) -> Result<Data, String> {
+    let data = from_envelope(json)?;
+    data.validate()?;
+    Ok(data)
+}
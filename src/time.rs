@@ -0,0 +1,162 @@
+/// Milliseconds since the Unix epoch.
+///
+/// On the server this reads the system clock, and in the browser JavaScript's `Date.now()`.
+pub fn now_ms() -> i64 {
+    #[cfg(feature = "ssr")]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as i64)
+            .unwrap_or_default()
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        js_sys::Date::now() as i64
+    }
+}
+
+/// Describes how long ago `then_ms` was, as of `now_ms`, such as "3m ago".
+///
+/// Only the largest whole unit is given. Times in the future, as can happen when the server's
+/// and browser's clocks disagree, are treated as just now.
+pub fn relative_time(now_ms: i64, then_ms: i64) -> String {
+    let seconds = (now_ms - then_ms).max(0) / 1000;
+    match seconds {
+        0..60 => "just now".to_string(),
+        60..3_600 => format!("{}m ago", seconds / 60),
+        3_600..86_400 => format!("{}h ago", seconds / 3_600),
+        _ => format!("{}d ago", seconds / 86_400),
+    }
+}
+
+const DAY_MS: i64 = 86_400_000;
+
+/// Parses a date as given by `<input type="date">`, such as "2024-03-01", into milliseconds since
+/// the Unix epoch at midnight UTC. An empty or malformed date gives `None`, as does one that
+/// doesn't exist, such as "2025-02-29", rather than rolling over into the next month. Years are
+/// held to the four digits `format_date` writes, which also keeps the milliseconds well within
+/// an `i64`.
+pub fn parse_date(date: &str) -> Option<i64> {
+    let mut parts = date.splitn(3, '-');
+    let year = parts.next()?.parse::<i64>().ok()?;
+    let month = parts.next()?.parse::<i64>().ok()?;
+    let day = parts.next()?.parse::<i64>().ok()?;
+    if !(1..=9999).contains(&year) || !(1..=12).contains(&month) {
+        return None;
+    }
+    if !(1..=days_in_month(year, month)).contains(&day) {
+        return None;
+    }
+    Some(days_from_civil(year, month, day) * DAY_MS)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Formats milliseconds since the Unix epoch as the UTC date `<input type="date">` expects, the
+/// inverse of [`parse_date`].
+pub fn format_date(ms: i64) -> String {
+    let (year, month, day) = civil_from_days(ms.div_euclid(DAY_MS));
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Whether a due date has passed, as of `now_ms`. Items are due by the end of their day, so they
+/// aren't overdue until the day after.
+pub fn is_overdue(now_ms: i64, due_ms: i64) -> bool {
+    now_ms >= due_ms + DAY_MS
+}
+
+// Converting between dates and days since the epoch follows Howard Hinnant's `days_from_civil`
+// and `civil_from_days`, which work in 400 year eras so as to avoid special-casing leap years.
+// See https://howardhinnant.github.io/date_algorithms.html
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates_round_trip() {
+        for date in [
+            "1970-01-01",
+            "1969-12-31",
+            "2024-02-29",
+            "2025-12-31",
+            "0001-01-01",
+            "9999-12-31",
+        ] {
+            assert_eq!(parse_date(date).map(format_date).as_deref(), Some(date));
+        }
+    }
+
+    #[test]
+    fn days_past_the_end_of_the_month_are_refused() {
+        for date in [
+            "2025-02-31",
+            "2025-04-31",
+            "2025-01-32",
+            "2025-01-00",
+            "2025-13-01",
+        ] {
+            assert_eq!(parse_date(date), None, "{date}");
+        }
+    }
+
+    #[test]
+    fn leap_days_only_parse_in_leap_years() {
+        assert!(parse_date("2024-02-29").is_some());
+        assert!(parse_date("2000-02-29").is_some());
+        assert_eq!(parse_date("2025-02-29"), None);
+        assert_eq!(parse_date("1900-02-29"), None);
+    }
+
+    #[test]
+    fn huge_years_are_refused_rather_than_overflowing() {
+        assert_eq!(parse_date("10000-01-01"), None);
+        assert_eq!(parse_date("9223372036854775807-01-01"), None);
+        assert_eq!(parse_date("0000-01-01"), None);
+        assert_eq!(parse_date(""), None);
+    }
+
+    #[test]
+    fn items_become_overdue_once_their_day_is_over() {
+        let due = parse_date("2025-03-01").unwrap();
+        let next_day = parse_date("2025-03-02").unwrap();
+        assert!(!is_overdue(due, due));
+        assert!(!is_overdue(next_day - 1, due));
+        assert!(is_overdue(next_day, due));
+        assert!(!is_overdue(due - 1, due));
+    }
+}